@@ -48,11 +48,20 @@ fn large_decode(b: &mut Bencher) {
     });
 }
 
+fn large_validate(b: &mut Bencher) {
+    let mut data = vec![0x4e00u16; 585144];
+    data[585143] = 0x3d05;
+    b.iter(|| {
+        black_box(Base16384::validate(black_box(&data)));
+    });
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("small encode", small_encode);
     c.bench_function("small decode", small_decode);
     c.bench_function("large encode", large_encode);
     c.bench_function("large decode", large_decode);
+    c.bench_function("large validate", large_validate);
 }
 
 fn small_encode_utf8(b: &mut Bencher) {
@@ -109,11 +118,24 @@ fn large_decode_utf8(b: &mut Bencher) {
     });
 }
 
+fn large_validate_utf8(b: &mut Bencher) {
+    let mut data = std::iter::repeat([0xE4, 0xB8, 0x80])
+        .take(585143)
+        .flatten()
+        .collect::<Vec<_>>();
+    data.extend([0xE3, 0xB4, 0x85]);
+    let data = String::from_utf8(data).unwrap();
+    b.iter(|| {
+        black_box(Base16384Utf8::validate(black_box(&data)));
+    });
+}
+
 pub fn criterion_benchmark_utf8(c: &mut Criterion) {
     c.bench_function("small encode", small_encode);
     c.bench_function("small decode", small_decode);
     c.bench_function("large encode", large_encode);
     c.bench_function("large decode", large_decode);
+    c.bench_function("large validate utf8", large_validate_utf8);
 }
 
 criterion_group!(benches, criterion_benchmark, criterion_benchmark_utf8);
@@ -53,10 +53,27 @@
         cargoPackage = craneLib.buildPackage (commonArgs // {
           inherit cargoArtifacts;
         });
+
+        # The optional `ct` and `zeroize` features.
+        featureArgs = commonArgs // {
+          cargoExtraArgs = "--locked --features ct,zeroize";
+        };
+        cargoArtifactsFeatures = craneLib.buildDepsOnly (featureArgs // {
+          pname = "base16384-deps-features";
+        });
+        cargoClippyFeatures = craneLib.cargoClippy (featureArgs // {
+          cargoArtifacts = cargoArtifactsFeatures;
+          cargoClippyExtraArgs = "--all-targets -- --deny warnings";
+        });
+        cargoTestFeatures = craneLib.cargoTest (featureArgs // {
+          cargoArtifacts = cargoArtifactsFeatures;
+        });
       in
       rec {
         packages.default = cargoPackage;
-        checks = { inherit cargoPackage cargoClippy; };
+        checks = {
+          inherit cargoPackage cargoClippy cargoClippyFeatures cargoTestFeatures;
+        };
 
         devShells.default = pkgs.mkShell {
           packages = [
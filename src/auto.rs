@@ -0,0 +1,118 @@
+//! Decoding with automatic detection of the input representation.
+//!
+//! See [`decode_auto`] and [`sniff`].
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::error::Base16384DecodeError;
+use crate::Base16384;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::Base16384Utf8;
+
+/// The representation of Base16384 text as bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Representation {
+    /// UTF-8, see [`Base16384Utf8`](crate::Base16384Utf8).
+    Utf8,
+    /// UTF-16 in big endian, the output of the base16384 C tool.
+    Utf16Be,
+    /// UTF-16 in little endian.
+    Utf16Le,
+}
+
+/// The UTF-8 byte order mark.
+const BOM_UTF8: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+/// Detects the representation of the given Base16384 bytes.
+///
+/// A byte order mark decides the representation if present. Otherwise, the lead bytes
+/// of the alphabet are checked: `E3..=E8` for each character in UTF-8 (`E3` being the
+/// padding), or `4E..=8D` (and `3D` for the padding) for the high byte of each code
+/// unit in UTF-16. Input matching none of them is assumed to be UTF-16BE, like the
+/// base16384 C tool does, and empty input is UTF-8.
+///
+/// Returns the detected representation, and the length of the byte order mark.
+///
+/// # Examples
+/// ```
+/// use base16384::auto::{sniff, Representation};
+/// use base16384::{Base16384, Base16384Utf8};
+///
+/// let utf8 = Base16384Utf8::encode(b"12345678");
+/// assert_eq!(sniff(utf8.as_bytes()), (Representation::Utf8, 0));
+///
+/// let utf16le = Base16384::encode_utf16le(b"12345678", false);
+/// assert_eq!(sniff(&utf16le), (Representation::Utf16Le, 0));
+///
+/// let utf16be = Base16384::encode_utf16be(b"12345678", true);
+/// assert_eq!(sniff(&utf16be), (Representation::Utf16Be, 2));
+/// ```
+pub fn sniff(data: &[u8]) -> (Representation, usize) {
+    if data.starts_with(&BOM_UTF8) {
+        return (Representation::Utf8, BOM_UTF8.len());
+    }
+    if data.starts_with(&Base16384::BOM_BE) {
+        return (Representation::Utf16Be, 2);
+    }
+    if data.starts_with(&Base16384::BOM_LE) {
+        return (Representation::Utf16Le, 2);
+    }
+
+    let utf8 = |b: &u8| (0xE3..=0xE8).contains(b);
+    let utf16 = |b: &u8| *b == 0x3D || (0x4E..=0x8D).contains(b);
+    let representation = if data.iter().step_by(3).all(utf8) {
+        Representation::Utf8
+    } else if data.iter().step_by(2).all(utf16) {
+        Representation::Utf16Be
+    } else if data.iter().skip(1).step_by(2).all(utf16) {
+        Representation::Utf16Le
+    } else {
+        Representation::Utf16Be
+    };
+    (representation, 0)
+}
+
+/// Decodes the given Base16384 bytes in any representation into a new allocated vector.
+///
+/// The representation is detected by [`sniff`], and returned along with the decoded
+/// data. A byte order mark is skipped, and the index of an invalid character is the
+/// byte index in the input.
+///
+/// # Examples
+/// ```
+/// use base16384::auto::{decode_auto, Representation};
+/// use base16384::{Base16384, Base16384Utf8};
+///
+/// let utf8 = Base16384Utf8::encode(b"12345678");
+/// let (decoded, representation) = decode_auto(utf8.as_bytes()).unwrap();
+/// assert_eq!(decoded, b"12345678");
+/// assert_eq!(representation, Representation::Utf8);
+///
+/// let utf16be = Base16384::encode_utf16be(b"12345678", true);
+/// let (decoded, representation) = decode_auto(&utf16be).unwrap();
+/// assert_eq!(decoded, b"12345678");
+/// assert_eq!(representation, Representation::Utf16Be);
+/// ```
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+pub fn decode_auto(
+    data: &[u8],
+) -> Result<(alloc::vec::Vec<u8>, Representation), Base16384DecodeError> {
+    let (representation, offset) = sniff(data);
+    let decoded = match representation {
+        Representation::Utf8 => {
+            let bytes = &data[offset..];
+            let decoded = match core::str::from_utf8(bytes) {
+                Ok(text) => Base16384Utf8::decode(text),
+                // Invalid UTF-8 is never in the alphabet, so it is found by the scan.
+                Err(_) => Err(Base16384Utf8::first_error(
+                    bytes,
+                    Base16384Utf8::trailing_padding(bytes),
+                )
+                .unwrap_or(Base16384DecodeError::InvalidLength)),
+            };
+            decoded.map_err(|err| err.offset(offset))?
+        }
+        Representation::Utf16Be => Base16384::decode_utf16be(data)?,
+        Representation::Utf16Le => Base16384::decode_utf16le(data)?,
+    };
+    Ok((decoded, representation))
+}
@@ -0,0 +1,232 @@
+//! A common interface for the Base16384 text representations.
+//!
+//! See [`Codec`].
+
+use crate::error::{Base16384DecodeError, Base16384EncodeError};
+use crate::{Base16384, Base16384Utf8};
+
+/// A Base16384 text representation, implemented by [`Base16384`] and [`Base16384Utf8`].
+///
+/// The methods mirror the inherent ones of the implementors, on slices of
+/// [`Codec::Unit`], so code can be generic over the representation.
+///
+/// # Examples
+/// ```
+/// use base16384::{Base16384, Base16384Utf8, Codec};
+///
+/// fn roundtrip<C: Codec>(data: &[u8]) -> Vec<u8> {
+///     let mut encoded = vec![C::Unit::default(); C::encode_len(data.len())];
+///     let encoded = C::encode_to_slice(data, &mut encoded);
+///     let mut decoded = vec![0u8; C::try_decode_len(encoded.len(), C::trailing_padding(encoded)).unwrap()];
+///     C::decode_to_slice(encoded, &mut decoded).unwrap().to_vec()
+/// }
+///
+/// assert_eq!(roundtrip::<Base16384>(b"12345678"), b"12345678");
+/// assert_eq!(roundtrip::<Base16384Utf8>(b"12345678"), b"12345678");
+/// ```
+pub trait Codec {
+    /// The code unit of the encoded text, `u16` for UTF-16 and `u8` for UTF-8.
+    type Unit: Copy + Default + core::fmt::Debug + Eq + 'static;
+
+    /// The number of code units in a Base16384 character.
+    const CHAR_LEN: usize;
+
+    /// Returns the minimum number of code units needed to encode the given number of
+    /// bytes.
+    fn encode_len(data_len: usize) -> usize;
+
+    /// Returns the minimum number of bytes needed to decode the given number of code
+    /// units. The given offset is the padding code point of the last chunk (if exists).
+    ///
+    /// The result is meaningless if no valid Base16384 data has the given length and
+    /// padding code point, see [`Codec::try_decode_len`].
+    fn decode_len(data_len: usize, padding: Option<u16>) -> usize;
+
+    /// Returns the number of bytes needed to decode the given number of code units, or
+    /// an error if no valid Base16384 data has the given length and padding code point.
+    fn try_decode_len(data_len: usize, padding: Option<u16>)
+        -> Result<usize, Base16384DecodeError>;
+
+    /// Gets the padding code point at the end of the given encoded data (if exists).
+    fn trailing_padding(data: &[Self::Unit]) -> Option<u16>;
+
+    /// Encodes the given data as Base16384 into the given buffer.
+    ///
+    /// # Panics
+    /// Panics if the buffer is too small. Use [`Codec::encode_len`] to get the required
+    /// capacity.
+    fn encode_to_slice<'a>(data: &[u8], buf: &'a mut [Self::Unit]) -> &'a [Self::Unit];
+
+    /// Decodes the given Base16384 data into the given buffer.
+    ///
+    /// Returns [`Base16384DecodeError::BufferTooSmall`] if the buffer is too small. Use
+    /// [`Codec::decode_len`] to get the required capacity.
+    fn decode_to_slice<'a>(
+        data: &[Self::Unit],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError>;
+
+    /// Encodes the given data as Base16384 into the given buffer, or returns an error if
+    /// the buffer is too small.
+    fn try_encode_to_slice<'a>(
+        data: &[u8],
+        buf: &'a mut [Self::Unit],
+    ) -> Result<&'a [Self::Unit], Base16384EncodeError> {
+        let needed = Self::encode_len(data.len());
+        if buf.len() < needed {
+            return Err(Base16384EncodeError::BufferTooSmall {
+                needed,
+                available: buf.len(),
+            });
+        }
+        Ok(Self::encode_to_slice(data, buf))
+    }
+
+    /// Decodes the given Base16384 data into the given buffer, or returns an error if the
+    /// buffer is too small.
+    ///
+    /// This is the same as [`Codec::decode_to_slice`], which checks the size of the
+    /// buffer as well.
+    #[deprecated(note = "use `decode_to_slice`, which returns the same errors")]
+    fn try_decode_to_slice<'a>(
+        data: &[Self::Unit],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        Self::decode_to_slice(data, buf)
+    }
+}
+
+impl Codec for Base16384 {
+    type Unit = u16;
+
+    const CHAR_LEN: usize = 1;
+
+    #[inline]
+    fn encode_len(data_len: usize) -> usize {
+        Base16384::encode_len(data_len)
+    }
+
+    #[inline]
+    fn decode_len(data_len: usize, padding: Option<u16>) -> usize {
+        Base16384::decode_len(data_len, padding)
+    }
+
+    #[inline]
+    fn try_decode_len(
+        data_len: usize,
+        padding: Option<u16>,
+    ) -> Result<usize, Base16384DecodeError> {
+        Base16384::try_decode_len(data_len, padding)
+    }
+
+    #[inline]
+    fn trailing_padding(data: &[u16]) -> Option<u16> {
+        data.last().cloned().and_then(Base16384::padding)
+    }
+
+    #[inline]
+    fn encode_to_slice<'a>(data: &[u8], buf: &'a mut [u16]) -> &'a [u16] {
+        Base16384::encode_to_slice(data, buf)
+    }
+
+    #[inline]
+    fn decode_to_slice<'a>(
+        data: &[u16],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        Base16384::decode_to_slice(data, buf)
+    }
+}
+
+impl Codec for Base16384Utf8 {
+    type Unit = u8;
+
+    const CHAR_LEN: usize = 3;
+
+    #[inline]
+    fn encode_len(data_len: usize) -> usize {
+        Base16384Utf8::encode_len(data_len)
+    }
+
+    #[inline]
+    fn decode_len(data_len: usize, padding: Option<u16>) -> usize {
+        Base16384Utf8::decode_len(data_len, padding)
+    }
+
+    #[inline]
+    fn try_decode_len(
+        data_len: usize,
+        padding: Option<u16>,
+    ) -> Result<usize, Base16384DecodeError> {
+        Base16384Utf8::try_decode_len(data_len, padding)
+    }
+
+    #[inline]
+    fn trailing_padding(data: &[u8]) -> Option<u16> {
+        Base16384Utf8::trailing_padding(data)
+    }
+
+    #[inline]
+    fn encode_to_slice<'a>(data: &[u8], buf: &'a mut [u8]) -> &'a [u8] {
+        let len = Base16384Utf8::encode_to_bytes(data, buf);
+        &buf[..len]
+    }
+
+    #[inline]
+    fn decode_to_slice<'a>(
+        data: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        Base16384Utf8::decode_bytes(data, buf)
+    }
+}
+
+/// Returns the number of bytes decoded from the last chunk, and the number of
+/// characters in it including the padding, for the given padding code point.
+#[inline]
+pub(crate) fn last_chunk(padding: u16) -> Option<(usize, usize)> {
+    let padding_size = padding.wrapping_sub(Base16384::PADDING_OFFSET);
+    let last_chunk_size = match padding_size {
+        0 => 1,
+        1 => 2,
+        2 | 3 => 3,
+        4 | 5 => 4,
+        6 => 5,
+        _ => return None,
+    };
+    Some((padding_size as usize, last_chunk_size))
+}
+
+/// Checks that a buffer of `available` bytes can hold the `needed` decoded bytes.
+#[inline]
+pub(crate) fn check_buffer(needed: usize, available: usize) -> Result<(), Base16384DecodeError> {
+    if available < needed {
+        return Err(Base16384DecodeError::BufferTooSmall { needed, available });
+    }
+    Ok(())
+}
+
+/// Splits the encoded data into the full chunks and the partial last chunk without the
+/// padding code point, and returns them with the number of bytes decoded from the
+/// latter.
+///
+/// `char_len` is the number of code units in a character, see [`Codec::CHAR_LEN`].
+#[inline]
+pub(crate) fn split_last_chunk<T>(
+    data: &[T],
+    padding: Option<u16>,
+    char_len: usize,
+) -> Result<(&[T], &[T], usize), Base16384DecodeError> {
+    let (padding_size, last_chunk_size) = match padding {
+        Some(padding) => last_chunk(padding).ok_or(Base16384DecodeError::InvalidLength)?,
+        None => (0, 0),
+    };
+    let split = data
+        .len()
+        .checked_sub(last_chunk_size * char_len)
+        .filter(|split| split % (4 * char_len) == 0)
+        .ok_or(Base16384DecodeError::InvalidLength)?;
+    let (chunks, last) = data.split_at(split);
+    let last = &last[..last.len().saturating_sub(char_len)];
+    Ok((chunks, last, padding_size))
+}
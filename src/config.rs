@@ -0,0 +1,529 @@
+//! Padding and strictness configuration.
+//!
+//! By default, the last chunk of the encoded data is followed by a padding code point
+//! telling how many bytes it holds, if the data length is not a multiple of 7. A
+//! [`Config`] makes the padding optional or leaves it out, or rejects non-canonical
+//! input, and is accepted by the `*_with_config` methods of [`Base16384`] and
+//! [`Base16384Utf8`].
+
+use crate::error::Base16384DecodeError;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::utils;
+use crate::utils::Scratch;
+use crate::{codec, Base16384, Base16384Utf8};
+
+/// How the padding code point is handled when decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodePadding {
+    /// The padding code point is required if the decoded length is not a multiple of 7.
+    ///
+    /// This is the default, and the behavior of the methods without a config.
+    Required,
+    /// The padding code point is optional.
+    ///
+    /// Without it, the partial last chunk decodes to all the whole bytes it holds, which
+    /// may be more than the encoded bytes, e.g. 2 bytes take 2 code units and decode to
+    /// 3 bytes. In strict mode, a partial last chunk without padding is rejected.
+    Indifferent,
+    /// The padding code point is not allowed, and the decoded length is supplied out of
+    /// band.
+    Unpadded(usize),
+}
+
+/// Padding and strictness configuration for encoding and decoding.
+///
+/// # Examples
+/// ```
+/// use base16384::config::{Config, DecodePadding};
+/// use base16384::Base16384;
+///
+/// let data = b"12345678";
+/// let config = Config::new().with_encode_padding(false);
+/// let encoded = Base16384::encode_with_config(data, config);
+/// assert_eq!(String::from_utf16(&encoded).unwrap(), "婌焳廔萷尀");
+///
+/// let config = config.with_decode_padding(DecodePadding::Unpadded(data.len()));
+/// let decoded = Base16384::decode_with_config(&encoded, config).unwrap();
+/// assert_eq!(decoded, data);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Config {
+    encode_padding: bool,
+    decode_padding: DecodePadding,
+    strict: bool,
+}
+
+impl Config {
+    /// Creates the default config, which encodes with padding and requires it when
+    /// decoding, in non-strict mode.
+    pub const fn new() -> Self {
+        Self {
+            encode_padding: true,
+            decode_padding: DecodePadding::Required,
+            strict: false,
+        }
+    }
+
+    /// Sets whether the padding code point is written when encoding.
+    pub const fn with_encode_padding(self, padding: bool) -> Self {
+        Self {
+            encode_padding: padding,
+            ..self
+        }
+    }
+
+    /// Sets how the padding code point is handled when decoding.
+    pub const fn with_decode_padding(self, padding: DecodePadding) -> Self {
+        Self {
+            decode_padding: padding,
+            ..self
+        }
+    }
+
+    /// Returns whether the padding code point is written when encoding.
+    pub const fn encode_padding(&self) -> bool {
+        self.encode_padding
+    }
+
+    /// Returns how the padding code point is handled when decoding.
+    pub const fn decode_padding(&self) -> DecodePadding {
+        self.decode_padding
+    }
+
+    /// Sets whether decoding only accepts the canonical encoding of the decoded bytes.
+    ///
+    /// In strict mode, the bits of the last chunk after the decoded bytes must be zero,
+    /// and the padding code point of an empty last chunk (`㴀`) is rejected, so that
+    /// different inputs never decode to the same bytes. With
+    /// [`DecodePadding::Indifferent`], a partial last chunk must be padded. Errors are
+    /// reported as [`Base16384DecodeError::NonCanonical`].
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::error::Base16384DecodeError;
+    /// use base16384::Base16384;
+    ///
+    /// // The last bit of '婍' is discarded when decoding 1 byte.
+    /// let data = "婍㴁".encode_utf16().collect::<Vec<_>>();
+    /// assert_eq!(Base16384::decode(&data).unwrap(), b"1");
+    ///
+    /// let config = Config::new().with_strict(true);
+    /// let err = Base16384::decode_with_config(&data, config).unwrap_err();
+    /// assert_eq!(err, Base16384DecodeError::NonCanonical { index: 0 });
+    /// ```
+    pub const fn with_strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Returns whether decoding only accepts the canonical encoding.
+    pub const fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Checks in strict mode that the last chunk, decoded to 7 bytes, only holds `len`
+    /// bytes, and that its padding code point is not the one of an empty chunk.
+    fn check_canonical(
+        &self,
+        decoded: &[u8],
+        len: usize,
+        padding: Option<u16>,
+        index: usize,
+    ) -> Result<(), Base16384DecodeError> {
+        let empty = padding == Some(Base16384::PADDING_OFFSET);
+        if self.strict && (empty || decoded[len..].iter().any(|&b| b != 0)) {
+            return Err(Base16384DecodeError::NonCanonical { index });
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bytes decoded from the given number of characters, each
+    /// taking `char_len` code units.
+    fn decoded_len(
+        &self,
+        chars: usize,
+        padding: Option<u16>,
+        char_len: usize,
+    ) -> Result<usize, Base16384DecodeError> {
+        match (self.decode_padding, padding) {
+            (DecodePadding::Required, _) | (DecodePadding::Indifferent, Some(_)) => {
+                Base16384::try_decode_len(chars, padding)
+            }
+            // Only the padded encoding of a partial last chunk is canonical.
+            (DecodePadding::Indifferent, None) if self.strict && chars % 4 != 0 => {
+                Err(Base16384DecodeError::NonCanonical {
+                    index: (chars - 1) * char_len,
+                })
+            }
+            (DecodePadding::Indifferent, None) => Ok(chars / 4 * 7 + chars % 4 * 14 / 8),
+            (DecodePadding::Unpadded(_), Some(padding)) => {
+                Err(Base16384DecodeError::InvalidCharacter {
+                    index: (chars - 1) * char_len,
+                    value: padding as u32,
+                    padding_position: true,
+                })
+            }
+            (DecodePadding::Unpadded(len), None) if encode_chars(len, false) == chars => Ok(len),
+            (DecodePadding::Unpadded(_), None) => Err(Base16384DecodeError::InvalidLength),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the number of characters needed to encode the given number of bytes.
+const fn encode_chars(data_len: usize, padding: bool) -> usize {
+    let chars = Base16384::encode_len(data_len);
+    if !padding && data_len % 7 != 0 {
+        chars - 1
+    } else {
+        chars
+    }
+}
+
+impl Base16384 {
+    /// Returns the number of u16s needed to encode the given number of bytes with the
+    /// given config.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::Base16384;
+    ///
+    /// let config = Config::new().with_encode_padding(false);
+    /// assert_eq!(Base16384::encode_len_with_config(8, config), 5);
+    /// assert_eq!(Base16384::encode_len_with_config(7, config), 4);
+    /// ```
+    #[inline]
+    pub const fn encode_len_with_config(data_len: usize, config: Config) -> usize {
+        encode_chars(data_len, config.encode_padding)
+    }
+
+    /// Encodes the given data as Base16384 in a new allocated vector with the given
+    /// config.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::Base16384;
+    ///
+    /// let config = Config::new().with_encode_padding(false);
+    /// let encoded = Base16384::encode_with_config(b"12345678", config);
+    /// assert_eq!(String::from_utf16(&encoded).unwrap(), "婌焳廔萷尀");
+    /// ```
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn encode_with_config(data: &[u8], config: Config) -> alloc::vec::Vec<u16> {
+        let mut result = alloc::vec![0u16; Self::encode_len_with_config(data.len(), config)];
+        Self::encode_to_slice_with_config(data, &mut result, config);
+        result
+    }
+
+    /// Encodes the given data as Base16384 into the given buffer with the given config.
+    ///
+    /// # Panics
+    /// Panics if the buffer is too small. Use [`Base16384::encode_len_with_config`] to
+    /// get the required capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::Base16384;
+    ///
+    /// let config = Config::new().with_encode_padding(false);
+    /// let mut buf = [0u16; 5];
+    /// let encoded = Base16384::encode_to_slice_with_config(b"12345678", &mut buf, config);
+    /// assert_eq!(String::from_utf16(encoded).unwrap(), "婌焳廔萷尀");
+    /// ```
+    pub fn encode_to_slice_with_config<'a>(
+        data: &[u8],
+        buf: &'a mut [u16],
+        config: Config,
+    ) -> &'a [u16] {
+        if config.encode_padding {
+            return Self::encode_to_slice(data, buf);
+        }
+        let capacity = Self::encode_len_with_config(data.len(), config);
+        assert!(buf.len() >= capacity, "buffer is too small");
+
+        let (chunks, remainder) = data.split_at(data.len() / 7 * 7);
+        let i = Self::encode_to_slice(chunks, buf).len();
+        let mut tmp = Scratch::new([0u16; 4]);
+        let encoded = match remainder.len() {
+            0 => &[][..],
+            _ => Self::encode_remainder(remainder, &mut tmp),
+        };
+        buf[i..i + encoded.len()].copy_from_slice(encoded);
+        &buf[..i + encoded.len()]
+    }
+
+    /// Returns the number of bytes decoded from the given Base16384 data with the given
+    /// config, or an error if the data has an invalid length or padding.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::{Config, DecodePadding};
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳廔萷尀".encode_utf16().collect::<Vec<_>>();
+    /// let config = Config::new().with_decode_padding(DecodePadding::Indifferent);
+    /// assert_eq!(Base16384::decode_len_with_config(&data, config), Ok(8));
+    ///
+    /// let config = Config::new().with_decode_padding(DecodePadding::Unpadded(8));
+    /// assert_eq!(Base16384::decode_len_with_config(&data, config), Ok(8));
+    /// ```
+    pub fn decode_len_with_config(
+        data: &[u16],
+        config: Config,
+    ) -> Result<usize, Base16384DecodeError> {
+        let padding = data.last().cloned().and_then(Self::padding);
+        config.decoded_len(data.len(), padding, 1)
+    }
+
+    /// Decodes the given Base16384 data into a new allocated vector with the given
+    /// config.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::{Config, DecodePadding};
+    /// use base16384::Base16384;
+    ///
+    /// let config = Config::new().with_decode_padding(DecodePadding::Indifferent);
+    /// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// let decoded = Base16384::decode_with_config(&data, config).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    ///
+    /// let data = "婌焳廔萷尀".encode_utf16().collect::<Vec<_>>();
+    /// let decoded = Base16384::decode_with_config(&data, config).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn decode_with_config(
+        data: &[u16],
+        config: Config,
+    ) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        let mut result = alloc::vec![0u8; Self::decode_len_with_config(data, config)?];
+        if let Err(err) = Self::decode_to_slice_with_config(data, &mut result, config) {
+            // The chunks decoded before the error are left in the vector.
+            utils::wipe_vec(&mut result);
+            return Err(err);
+        }
+        Ok(result)
+    }
+
+    /// Decodes the given Base16384 data into the given buffer with the given config.
+    ///
+    /// Returns [`Base16384DecodeError::BufferTooSmall`] if the buffer is too small. Use
+    /// [`Base16384::decode_len_with_config`] to get the required capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::{Config, DecodePadding};
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳廔萷尀".encode_utf16().collect::<Vec<_>>();
+    /// let config = Config::new().with_decode_padding(DecodePadding::Unpadded(8));
+    /// let mut buf = [0u8; 8];
+    /// let decoded = Base16384::decode_to_slice_with_config(&data, &mut buf, config).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    pub fn decode_to_slice_with_config<'a>(
+        data: &[u16],
+        buf: &'a mut [u8],
+        config: Config,
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        let padding = data.last().cloned().and_then(Self::padding);
+        // Errors are located in the whole data, since chunks hold no position.
+        let first_error = |err| Self::first_error(data, padding).unwrap_or(err);
+        let capacity = Self::decode_len_with_config(data, config).map_err(first_error)?;
+        codec::check_buffer(capacity, buf.len())?;
+
+        // A last chunk of 4 characters decodes to 6 bytes with an out of band length.
+        let (chunks, last) = data.split_at(capacity / 7 * 4);
+        let remainder = &last[..last.len() - padding.is_some() as usize];
+        let i = Self::decode_to_slice(chunks, buf)
+            .map_err(first_error)?
+            .len();
+        if !last.is_empty() {
+            let mut tmp = Scratch::new([0u8; 7]);
+            let decoded = Self::decode_remainder(remainder, &mut tmp, 7).map_err(first_error)?;
+            let index = chunks.len() + remainder.len().saturating_sub(1);
+            config.check_canonical(decoded, capacity - i, padding, index)?;
+            buf[i..capacity].copy_from_slice(&decoded[..capacity - i]);
+        }
+        Ok(&buf[..capacity])
+    }
+}
+
+impl Base16384Utf8 {
+    /// Returns the number of bytes needed to encode the given number of bytes with the
+    /// given config.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let config = Config::new().with_encode_padding(false);
+    /// assert_eq!(Base16384Utf8::encode_len_with_config(8, config), 15);
+    /// ```
+    #[inline]
+    pub const fn encode_len_with_config(data_len: usize, config: Config) -> usize {
+        encode_chars(data_len, config.encode_padding) * 3
+    }
+
+    /// Encodes the given data as Base16384 in a new allocated [`String`] with the given
+    /// config.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let config = Config::new().with_encode_padding(false);
+    /// let encoded = Base16384Utf8::encode_with_config(b"12345678", config);
+    /// assert_eq!(encoded, "婌焳廔萷尀");
+    /// ```
+    ///
+    /// [`String`]: alloc::string::String
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn encode_with_config(data: &[u8], config: Config) -> alloc::string::String {
+        let mut result = alloc::vec![0u8; Self::encode_len_with_config(data.len(), config)];
+        // SAFETY: the buffer is all ASCII, and it is entirely overwritten with valid UTF-8.
+        let buf = unsafe { core::str::from_utf8_unchecked_mut(&mut result) };
+        Self::encode_to_slice_with_config(data, buf, config);
+        unsafe { alloc::string::String::from_utf8_unchecked(result) }
+    }
+
+    /// Encodes the given data as Base16384 into the given buffer with the given config.
+    ///
+    /// # Panics
+    /// Panics if the buffer is too small. Use [`Base16384Utf8::encode_len_with_config`]
+    /// to get the required capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::Config;
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let config = Config::new().with_encode_padding(false);
+    /// let mut buf = "A".repeat(15);
+    /// let encoded = Base16384Utf8::encode_to_slice_with_config(b"12345678", &mut buf, config);
+    /// assert_eq!(encoded, "婌焳廔萷尀");
+    /// ```
+    pub fn encode_to_slice_with_config<'a>(
+        data: &[u8],
+        buf: &'a mut str,
+        config: Config,
+    ) -> &'a str {
+        if config.encode_padding {
+            return Self::encode_to_slice(data, buf);
+        }
+        let capacity = Self::encode_len_with_config(data.len(), config);
+        assert!(buf.len() >= capacity, "buffer is too small");
+
+        let buf = unsafe { buf.as_bytes_mut() };
+        let (chunks, remainder) = data.split_at(data.len() / 7 * 7);
+        let i = Self::encode_to_bytes(chunks, buf);
+        let mut tmp = Scratch::new([0u8; 12]);
+        let encoded = match remainder.len() {
+            0 => &[][..],
+            _ => Self::encode_remainder(remainder, &mut tmp),
+        };
+        buf[i..i + encoded.len()].copy_from_slice(encoded);
+        unsafe { core::str::from_utf8_unchecked(&buf[..i + encoded.len()]) }
+    }
+
+    /// Returns the number of bytes decoded from the given utf8 data with the given
+    /// config, or an error if the data has an invalid length or padding.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::{Config, DecodePadding};
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let config = Config::new().with_decode_padding(DecodePadding::Indifferent);
+    /// assert_eq!(Base16384Utf8::decode_len_with_config("婌焳廔萷尀", config), Ok(8));
+    /// ```
+    pub fn decode_len_with_config(
+        data: &str,
+        config: Config,
+    ) -> Result<usize, Base16384DecodeError> {
+        let data = data.as_bytes();
+        if data.len() % 3 != 0 {
+            return Err(Base16384DecodeError::InvalidLength);
+        }
+        config.decoded_len(data.len() / 3, Self::trailing_padding(data), 3)
+    }
+
+    /// Decodes the given utf8 data as Base16384 in a new allocated vector with the given
+    /// config.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::{Config, DecodePadding};
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let config = Config::new().with_decode_padding(DecodePadding::Unpadded(8));
+    /// let decoded = Base16384Utf8::decode_with_config("婌焳廔萷尀", config).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn decode_with_config(
+        data: &str,
+        config: Config,
+    ) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        let mut result = alloc::vec![0u8; Self::decode_len_with_config(data, config)?];
+        if let Err(err) = Self::decode_to_slice_with_config(data, &mut result, config) {
+            // The chunks decoded before the error are left in the vector.
+            utils::wipe_vec(&mut result);
+            return Err(err);
+        }
+        Ok(result)
+    }
+
+    /// Decodes the given utf8 data as Base16384 into the given buffer with the given
+    /// config.
+    ///
+    /// Returns [`Base16384DecodeError::BufferTooSmall`] if the buffer is too small. Use
+    /// [`Base16384Utf8::decode_len_with_config`] to get the required capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::config::{Config, DecodePadding};
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let config = Config::new().with_decode_padding(DecodePadding::Indifferent);
+    /// let mut buf = [0u8; 8];
+    /// let decoded = Base16384Utf8::decode_to_slice_with_config("婌焳廔萷尀", &mut buf, config);
+    /// assert_eq!(decoded.unwrap(), b"12345678");
+    /// ```
+    pub fn decode_to_slice_with_config<'a>(
+        data: &str,
+        buf: &'a mut [u8],
+        config: Config,
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        let bytes = data.as_bytes();
+        let padding = Self::trailing_padding(bytes);
+        // Errors are located in the whole data, since chunks hold no position.
+        let first_error = |err| Self::first_error(bytes, padding).unwrap_or(err);
+        let capacity = Self::decode_len_with_config(data, config).map_err(first_error)?;
+        codec::check_buffer(capacity, buf.len())?;
+
+        // A last chunk of 4 characters decodes to 6 bytes with an out of band length.
+        let (chunks, last) = bytes.split_at(capacity / 7 * 12);
+        let remainder = &last[..last.len() - padding.is_some() as usize * 3];
+        let i = Self::decode_bytes(chunks, buf).map_err(first_error)?.len();
+        if !last.is_empty() {
+            let mut tmp = Scratch::new([0u8; 7]);
+            let decoded = Self::decode_remainder(remainder, &mut tmp, 7).map_err(first_error)?;
+            let index = chunks.len() + remainder.len().saturating_sub(3);
+            config.check_canonical(decoded, capacity - i, padding, index)?;
+            buf[i..capacity].copy_from_slice(&decoded[..capacity - i]);
+        }
+        Ok(&buf[..capacity])
+    }
+}
@@ -0,0 +1,80 @@
+//! Branch-free character checks, used when decoding with the `ct` feature.
+//!
+//! The validity of a chunk is folded into a single mask, so that decoding valid data
+//! takes the same time whatever the characters are. Only the error path, which locates
+//! the first invalid character, depends on the data.
+
+use crate::Base16384;
+
+/// Writes the offsets of the given code units from [`Base16384::START`], and returns a
+/// non-zero mask if any of them is outside of the alphabet.
+#[inline]
+pub(crate) fn unit_offsets(chunk: &[u16; 4], offsets: &mut [u16; 4]) -> u16 {
+    let mut invalid = 0;
+    for (offset, &c) in offsets.iter_mut().zip(chunk) {
+        // Code units below the alphabet wrap around to large offsets.
+        *offset = c.wrapping_sub(Base16384::START);
+        invalid |= *offset >> 14;
+    }
+    invalid
+}
+
+/// Writes the offsets of the code points of the given utf8 characters from
+/// [`Base16384::START`], and returns a non-zero mask if any of them is not a 3-byte
+/// sequence in the alphabet.
+#[inline]
+pub(crate) fn utf8_offsets(chunk: &[u8; 12], offsets: &mut [u16; 4]) -> u16 {
+    let mut invalid = 0;
+    for (i, offset) in offsets.iter_mut().enumerate() {
+        let (b0, b1, b2) = (chunk[i * 3], chunk[i * 3 + 1], chunk[i * 3 + 2]);
+        let c = ((b0 & 0x0F) as u16) << 12 | ((b1 & 0x3F) as u16) << 6 | (b2 & 0x3F) as u16;
+        *offset = c.wrapping_sub(Base16384::START);
+        // The alphabet only has 3-byte sequences, so checking the tag bits is enough.
+        invalid |= *offset >> 14
+            | ((b0 & 0xF0) ^ 0xE0) as u16
+            | ((b1 & 0xC0) ^ 0x80) as u16
+            | ((b2 & 0xC0) ^ 0x80) as u16;
+    }
+    invalid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base16384Utf8;
+
+    #[test]
+    fn unit_offsets_exhaustive() {
+        for c in 0..=u16::MAX {
+            // Each position of the chunk is checked in turn.
+            let mut chunk = [Base16384::START; 4];
+            chunk[c as usize % 4] = c;
+            let mut offsets = [0u16; 4];
+            let invalid = unit_offsets(&chunk, &mut offsets);
+            assert_eq!(invalid == 0, Base16384::is_valid_char(c), "{:#x}", c);
+            if invalid == 0 {
+                assert_eq!(offsets[c as usize % 4], c - Base16384::START);
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_offsets_exhaustive() {
+        for n in 0..1u32 << 24 {
+            let [_, b0, b1, b2] = n.to_be_bytes();
+            let i = n as usize % 4;
+            // Each position of a chunk of '一' is checked in turn.
+            let mut chunk = [
+                0xE4, 0xB8, 0x80, 0xE4, 0xB8, 0x80, 0xE4, 0xB8, 0x80, 0xE4, 0xB8, 0x80,
+            ];
+            chunk[i * 3..i * 3 + 3].copy_from_slice(&[b0, b1, b2]);
+            let mut offsets = [0u16; 4];
+            let invalid = utf8_offsets(&chunk, &mut offsets);
+            let valid = Base16384Utf8::valid_char([b0, b1, b2]);
+            assert_eq!(invalid == 0, valid.is_some(), "{:x?}", [b0, b1, b2]);
+            if let Some(c) = valid {
+                assert_eq!(offsets[i], c - Base16384::START);
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Heuristic detection of Base16384 text.
+//!
+//! See [`detect`] and [`detect_bytes`].
+
+use crate::auto::{sniff, Representation};
+use crate::config::Config;
+use crate::{codec, Base16384, Base16384Utf8};
+
+/// The result of [`detect`] or [`detect_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    /// How likely the input is Base16384, from 0 to 1.
+    pub confidence: f32,
+    /// The representation of the input.
+    pub representation: Representation,
+    /// The number of decoded bytes, if the input has a valid length and padding.
+    pub payload_len: Option<usize>,
+}
+
+/// Detects whether the given text is likely Base16384 rather than ordinary text.
+///
+/// The text must be entirely inside the alphabet, optionally followed by a padding code
+/// point, or the confidence is 0. Beyond that, the confidence depends on whether the
+/// length and the padding are consistent, on the length itself, and on the fraction of
+/// common hanzi, which is low in Base16384 since all 16384 characters are equally
+/// likely, but high in Chinese prose.
+///
+/// # Examples
+/// ```
+/// use base16384::detect::detect;
+/// use base16384::Base16384Utf8;
+///
+/// let text = Base16384Utf8::encode(b"some binary data, e.g. a key");
+/// let detection = detect(&text);
+/// assert!(detection.confidence > 0.8);
+/// assert_eq!(detection.payload_len, Some(28));
+///
+/// let detection = detect("中华人民共和国成立于一九四九年十月一日");
+/// assert!(detection.confidence < 0.2);
+/// assert_eq!(detection.payload_len, None);
+///
+/// assert_eq!(detect("hello").confidence, 0.0);
+/// ```
+pub fn detect(text: &str) -> Detection {
+    let padding = Base16384Utf8::trailing_padding(text.as_bytes());
+    let units = text
+        .chars()
+        .map(|c| u16::try_from(c as u32).unwrap_or(u16::MAX));
+    score(units, padding, Representation::Utf8)
+}
+
+/// Detects whether the given bytes are likely Base16384 rather than ordinary text.
+///
+/// The representation is detected by [`sniff`](crate::auto::sniff), and the text is
+/// then scored like [`detect`] does.
+///
+/// # Examples
+/// ```
+/// use base16384::auto::Representation;
+/// use base16384::detect::detect_bytes;
+/// use base16384::Base16384;
+///
+/// let encoded = Base16384::encode_utf16be(b"some binary data, e.g. a key", true);
+/// let detection = detect_bytes(&encoded);
+/// assert!(detection.confidence > 0.8);
+/// assert_eq!(detection.representation, Representation::Utf16Be);
+/// assert_eq!(detection.payload_len, Some(28));
+/// ```
+pub fn detect_bytes(data: &[u8]) -> Detection {
+    let (representation, offset) = sniff(data);
+    let data = &data[offset..];
+    let from_bytes = match representation {
+        Representation::Utf8 => {
+            return match core::str::from_utf8(data) {
+                Ok(text) => detect(text),
+                Err(_) => Detection {
+                    confidence: 0.0,
+                    representation,
+                    payload_len: None,
+                },
+            }
+        }
+        Representation::Utf16Be => u16::from_be_bytes,
+        Representation::Utf16Le => u16::from_le_bytes,
+    };
+    let units = data.chunks(2).map(|c| match *c {
+        [hi, lo] => from_bytes([hi, lo]),
+        _ => u16::MAX,
+    });
+    let padding = units.clone().next_back().and_then(Base16384::padding);
+    score(units, padding, representation)
+}
+
+fn score(
+    units: impl Iterator<Item = u16>,
+    padding: Option<u16>,
+    representation: Representation,
+) -> Detection {
+    let mut detection = Detection {
+        confidence: 0.0,
+        representation,
+        payload_len: None,
+    };
+
+    // The last chunk, for the check of canonical encoding.
+    let mut last = [0u16; 5];
+    let mut len = 0;
+    let mut common = 0;
+    let mut padded = false;
+    for c in units {
+        // Only the last character may be a padding code point.
+        if padded {
+            return detection;
+        }
+        if Base16384::is_valid_char(c) {
+            common += is_common(c) as usize;
+        } else if padding.is_some() && Base16384::padding(c).is_some() {
+            padded = true;
+        } else {
+            return detection;
+        }
+        last[len % 5] = c;
+        len += 1;
+    }
+    if len == 0 {
+        detection.payload_len = Some(0);
+        return detection;
+    }
+    let chars = len - padding.is_some() as usize;
+    // About 3% of the alphabet is common, but most of Chinese prose is. The count is
+    // smoothed so that a single common character does not rule out a short input.
+    let rarity = 1.0 - (common as f32 / (chars + 2) as f32 * 2.0).min(1.0);
+
+    detection.payload_len = payload_len(&last, len, padding);
+    let base = match (detection.payload_len, padding) {
+        (Some(_), Some(_)) => 0.6,
+        (Some(_), None) => 0.5,
+        (None, _) => 0.1,
+    };
+    let length = (chars.min(16) as f32) / 16.0 * 0.4;
+    detection.confidence = ((base + length) * rarity).min(1.0);
+    detection
+}
+
+/// Returns the decoded length if the length and padding are consistent, and the last
+/// chunk is canonical. `last` holds the last code units in a ring.
+fn payload_len(last: &[u16; 5], len: usize, padding: Option<u16>) -> Option<usize> {
+    let payload_len = Base16384::try_decode_len(len, padding).ok()?;
+    if let Some(padding) = padding {
+        let (_, last_chunk_size) = codec::last_chunk(padding)?;
+        let mut chunk = [0u16; 5];
+        for (i, c) in chunk[..last_chunk_size].iter_mut().enumerate() {
+            *c = last[(len - last_chunk_size + i) % 5];
+        }
+        let config = Config::new().with_strict(true);
+        let mut buf = [0u8; 7];
+        Base16384::decode_to_slice_with_config(&chunk[..last_chunk_size], &mut buf, config).ok()?;
+    }
+    Some(payload_len)
+}
+
+/// Returns whether the given alphabet character is a common hanzi.
+pub(crate) fn is_common(c: u16) -> bool {
+    let i = (c - Base16384::START) as usize;
+    COMMON_BITS[i / 64] & (1 << (i % 64)) != 0
+}
+
+/// Common hanzi in the alphabet, roughly in the order of frequency.
+const COMMON: &str = "的一是不了在人有我他个们中来上大为和国地到以说时要就出会可也你对生能而子\
+    得于着下自之年发后作用行所然家种事成方多经么去法学如同现当没动面起看定天分小部其些主样理心\
+    她本前开但因只从想实日军者意无力它与长把机十民第公此已工使情明性知全三又关点正业外将两高间\
+    由问很最重并物手应战向头文体政美相见被利什二等产或新己制身果加西斯月话合回特代内信表化老给\
+    世位次度门任常先海教儿原东声提立及比员解水名真论处走义各入几口认条平系气题活尔更别打女变四\
+    神总何电数安少报才结反受目太量再感建务做接必场件计管期市直德资命山金指克许统区保至队形社便\
+    空决治展马科司五基眼书非则听白却界达光放强即像难且权思王象完设式色路记南品住告类求据程北边\
+    死张该交规万取拉格望觉术领共确传师观清今切院让识候带导争运笑飞风步改收根干造言联持组每济车\
+    亲极林服快办议往元英士证近失转夫令准布始怎呢存未叫台单影具罗字爱击流备兵连调深商算质团集百\
+    价花党华城石级整府况亚请技际约示复病息究线似官火断精满支视消器容照须九增研写称企八功吗包片\
+    史委乎查轻易早曾除农找装广显吧阿李标谈吃图念六引历首医局突专费号尽另周较注语仅考落青随选列\
+    武红响虽推势参希古众构房半节土投某案黑维革划敌致陈律足态护七兴派孩验责营星够章音跟志底站严\
+    巴例防族供效续施留讲型料终答紧黄绝奇察母京段依批群项故按河米围江织害斗双境客纪采举杀攻父苏\
+    密低朝友诉止细愿千值仍男钱破网热助倒育属坐帐积";
+
+/// A bitmap of [`COMMON`], indexed by the offset in the alphabet.
+const COMMON_BITS: [u64; 256] = common_bits(COMMON.as_bytes());
+
+const fn common_bits(s: &[u8]) -> [u64; 256] {
+    let mut bits = [0u64; 256];
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] & 0xF0 == 0xE0 && i + 2 < s.len() {
+            let c = ((s[i] as u32 & 0x0F) << 12)
+                | ((s[i + 1] as u32 & 0x3F) << 6)
+                | (s[i + 2] as u32 & 0x3F);
+            if c >= Base16384::START as u32 && c <= Base16384::START as u32 + 0x3FFF {
+                let j = (c - Base16384::START as u32) as usize;
+                bits[j / 64] |= 1 << (j % 64);
+            }
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    bits
+}
@@ -5,9 +5,13 @@ use core::fmt::Display;
 use std::error::Error;
 
 /// Errors that can occur when decoding base16384.
-#[derive(Debug, PartialEq)]
+///
+/// Indexes are absolute positions in the whole input: the index of the code unit for
+/// UTF-16, and the byte index for UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Base16384DecodeError {
-    /// The input data has an invalid length.
+    /// The input data has an invalid length, and no padding code point.
     InvalidLength,
     /// The input data has an invalid character at the given index.
     InvalidCharacter {
@@ -15,17 +19,167 @@ pub enum Base16384DecodeError {
         ///
         /// In UTF-8, this is the byte index.
         index: usize,
+        /// The invalid code unit, or in UTF-8 the invalid code point. Bytes that are not
+        /// valid UTF-8 are reported as U+FFFD.
+        value: u32,
+        /// Whether the character is in the position of the padding code point, at the
+        /// end of the input.
+        padding_position: bool,
     },
+    /// The padding code point at the end of the input does not match the number of
+    /// characters in the last chunk.
+    InvalidPadding {
+        /// The index of the padding code point.
+        ///
+        /// In UTF-8, this is the byte index.
+        index: usize,
+        /// The padding code point.
+        value: u16,
+    },
+    /// A padding code point is followed by more data.
+    PaddingNotAtEnd {
+        /// The index of the padding code point.
+        ///
+        /// In UTF-8, this is the byte index.
+        index: usize,
+    },
+    /// The UTF-8 input ends in the middle of a character.
+    TruncatedCharacter {
+        /// The byte index of the truncated character.
+        index: usize,
+    },
+    /// The input data is not the canonical encoding of the decoded bytes, in strict
+    /// mode (see [`Config::with_strict`](crate::config::Config::with_strict)).
+    NonCanonical {
+        /// The index of the offending character in the input.
+        ///
+        /// In UTF-8, this is the byte index.
+        index: usize,
+    },
+    /// The output buffer is too small.
+    BufferTooSmall {
+        /// The number of bytes needed.
+        needed: usize,
+        /// The number of bytes available.
+        available: usize,
+    },
+}
+
+impl Base16384DecodeError {
+    /// Returns the index of the offending character in the input, if the error has one.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let err = Base16384Utf8::decode("婌焳廔萷x尀㴁").unwrap_err();
+    /// assert_eq!(err.index(), Some(12));
+    /// ```
+    pub fn index(&self) -> Option<usize> {
+        match *self {
+            Self::InvalidCharacter { index, .. }
+            | Self::InvalidPadding { index, .. }
+            | Self::PaddingNotAtEnd { index }
+            | Self::TruncatedCharacter { index }
+            | Self::NonCanonical { index } => Some(index),
+            Self::InvalidLength | Self::BufferTooSmall { .. } => None,
+        }
+    }
+
+    /// Maps the index of the offending character, e.g. from a chunk to the whole input.
+    pub(crate) fn map_index(mut self, f: impl FnOnce(usize) -> usize) -> Self {
+        match &mut self {
+            Self::InvalidCharacter { index, .. }
+            | Self::InvalidPadding { index, .. }
+            | Self::PaddingNotAtEnd { index }
+            | Self::TruncatedCharacter { index }
+            | Self::NonCanonical { index } => *index = f(*index),
+            Self::InvalidLength | Self::BufferTooSmall { .. } => {}
+        }
+        self
+    }
+
+    /// Adds `offset` to the index of the offending character.
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        self.map_index(|index| index + offset)
+    }
+
+    /// Marks an invalid character as in the padding position if it is the last one of
+    /// an input of the given length, where characters take `char_len` code units.
+    pub(crate) fn at_end(mut self, len: usize, char_len: usize) -> Self {
+        if let Self::InvalidCharacter {
+            index,
+            padding_position,
+            ..
+        } = &mut self
+        {
+            *padding_position = *index + char_len == len;
+        }
+        self
+    }
 }
 
 impl Display for Base16384DecodeError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidLength => write!(f, "invalid length"),
-            Self::InvalidCharacter { index } => write!(f, "invalid character at index {}", index),
+            Self::InvalidCharacter {
+                index,
+                value,
+                padding_position,
+            } => {
+                write!(f, "invalid character U+{:04X} at index {}", value, index)?;
+                if *padding_position {
+                    write!(f, " in the padding position")?;
+                }
+                Ok(())
+            }
+            Self::InvalidPadding { index, value } => {
+                write!(f, "invalid padding U+{:04X} at index {}", value, index)
+            }
+            Self::PaddingNotAtEnd { index } => {
+                write!(f, "padding not at the end at index {}", index)
+            }
+            Self::TruncatedCharacter { index } => {
+                write!(f, "truncated character at index {}", index)
+            }
+            Self::NonCanonical { index } => write!(f, "non-canonical character at index {}", index),
+            Self::BufferTooSmall { needed, available } => write!(
+                f,
+                "buffer is too small: {} needed, {} available",
+                needed, available
+            ),
         }
     }
 }
 
 #[cfg(feature = "std")]
 impl Error for Base16384DecodeError {}
+
+/// Errors that can occur when encoding base16384.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Base16384EncodeError {
+    /// The output buffer is too small.
+    BufferTooSmall {
+        /// The number of code units (or bytes, in UTF-8) needed.
+        needed: usize,
+        /// The number of code units (or bytes, in UTF-8) available.
+        available: usize,
+    },
+}
+
+impl Display for Base16384EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BufferTooSmall { needed, available } => write!(
+                f,
+                "buffer is too small: {} needed, {} available",
+                needed, available
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Base16384EncodeError {}
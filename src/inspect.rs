@@ -0,0 +1,239 @@
+//! Chunk by chunk reports on Base16384 data, for debugging.
+//!
+//! See [`Base16384::inspect`] and [`Base16384Utf8::inspect`].
+
+use core::fmt::Display;
+
+use crate::{codec, Base16384, Base16384Utf8};
+
+/// A report on Base16384 data, returned by [`Base16384::inspect`] and
+/// [`Base16384Utf8::inspect`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Report {
+    /// The number of full chunks of 4 characters, each decoding to 7 bytes.
+    pub chunks: usize,
+    /// The number of characters in the last chunk, if it is padded or partial, not
+    /// counting the padding code point.
+    pub remainder_len: usize,
+    /// The padding code point at the end of the data, if any.
+    pub padding: Option<u16>,
+    /// The number of decoded bytes, if the length of the data is consistent with the
+    /// padding.
+    pub payload_len: Option<usize>,
+    /// The problems found, in the order of their positions.
+    pub problems: alloc::vec::Vec<Problem>,
+}
+
+impl Report {
+    /// Returns whether no problem was found, in which case the data decodes to
+    /// [`payload_len`](Report::payload_len) bytes.
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "chunks: {}, remainder: {}",
+            self.chunks, self.remainder_len
+        )?;
+        match self.padding {
+            Some(padding) => write!(f, ", padding: U+{:04X}", padding)?,
+            None => write!(f, ", padding: none")?,
+        }
+        match self.payload_len {
+            Some(len) => write!(f, ", payload: {} bytes", len)?,
+            None => write!(f, ", payload: invalid length")?,
+        }
+        for problem in &self.problems {
+            write!(f, "\n{}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem found in Base16384 data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Problem {
+    /// The kind of the problem.
+    pub kind: ProblemKind,
+    /// The index of the offending character in the data, or the length of the data if
+    /// it ends too early.
+    ///
+    /// In UTF-8, this is the byte index.
+    pub index: usize,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at index {}", self.kind, self.index)
+    }
+}
+
+/// The kind of a [`Problem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProblemKind {
+    /// The character is outside of the alphabet.
+    InvalidCharacter,
+    /// A padding code point is followed by more characters.
+    PaddingNotAtEnd,
+    /// The number of characters does not match the padding code point. The index is the
+    /// one of the padding code point, or the end of the data.
+    InvalidLength,
+    /// The last chunk is not the canonical encoding of the decoded bytes: either its
+    /// unused bits are not zero, or it is an empty chunk. The data still decodes, but
+    /// is rejected in strict mode (see
+    /// [`Config::with_strict`](crate::config::Config::with_strict)).
+    NonCanonical,
+}
+
+impl Display for ProblemKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidCharacter => write!(f, "invalid character"),
+            Self::PaddingNotAtEnd => write!(f, "padding not at the end"),
+            Self::InvalidLength => write!(f, "invalid length"),
+            Self::NonCanonical => write!(f, "non-canonical character"),
+        }
+    }
+}
+
+impl Base16384 {
+    /// Inspects the given Base16384 data chunk by chunk, and reports every problem
+    /// found instead of stopping at the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::inspect::{Problem, ProblemKind};
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// let report = Base16384::inspect(&data);
+    /// assert!(report.is_valid());
+    /// assert_eq!((report.chunks, report.remainder_len), (1, 1));
+    /// assert_eq!(report.padding, Some(0x3d01));
+    /// assert_eq!(report.payload_len, Some(8));
+    ///
+    /// let data = "婌x廔萷尀㴁尀".encode_utf16().collect::<Vec<_>>();
+    /// let report = Base16384::inspect(&data);
+    /// assert_eq!(
+    ///     report.problems,
+    ///     [
+    ///         Problem { kind: ProblemKind::InvalidCharacter, index: 1 },
+    ///         Problem { kind: ProblemKind::PaddingNotAtEnd, index: 5 },
+    ///         Problem { kind: ProblemKind::InvalidLength, index: 7 },
+    ///     ]
+    /// );
+    /// ```
+    pub fn inspect(data: &[u16]) -> Report {
+        let padding = data.last().cloned().and_then(Self::padding);
+        let chars = &data[..data.len() - padding.is_some() as usize];
+        inspect(chars.iter().cloned().enumerate(), padding, chars.len())
+    }
+}
+
+impl Base16384Utf8 {
+    /// Inspects the given utf8 data as Base16384 chunk by chunk, and reports every
+    /// problem found instead of stopping at the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::inspect::{Problem, ProblemKind};
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let report = Base16384Utf8::inspect("婌焳廔萷封㴁");
+    /// assert_eq!(report.payload_len, Some(8));
+    /// assert_eq!(
+    ///     report.problems,
+    ///     [Problem { kind: ProblemKind::NonCanonical, index: 12 }]
+    /// );
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "chunks: 1, remainder: 1, padding: U+3D01, payload: 8 bytes\n\
+    ///      non-canonical character at index 12"
+    /// );
+    /// ```
+    pub fn inspect(data: &str) -> Report {
+        let padding = data.chars().next_back().and_then(|c| {
+            let c = u16::try_from(c as u32).ok()?;
+            Base16384::padding(c)
+        });
+        let chars = &data[..data.len() - padding.map_or(0, |_| 3)];
+        let units = chars
+            .char_indices()
+            .map(|(i, c)| (i, u16::try_from(c as u32).unwrap_or(u16::MAX)));
+        inspect(units, padding, chars.len())
+    }
+}
+
+/// Inspects the given code units other than the trailing padding code point, along with
+/// their indexes in the data. `end` is the index of the padding code point, or the
+/// length of the data if there is none.
+fn inspect(units: impl Iterator<Item = (usize, u16)>, padding: Option<u16>, end: usize) -> Report {
+    let mut problems = alloc::vec::Vec::new();
+    // The characters of the last chunk, with their indexes.
+    let mut last = [(0usize, 0u16); 4];
+    let mut n = 0;
+    for (i, c) in units {
+        if Base16384::padding(c).is_some() {
+            problems.push(Problem {
+                kind: ProblemKind::PaddingNotAtEnd,
+                index: i,
+            });
+        } else if !Base16384::is_valid_char(c) {
+            problems.push(Problem {
+                kind: ProblemKind::InvalidCharacter,
+                index: i,
+            });
+        }
+        last[n % 4] = (i, c);
+        n += 1;
+    }
+
+    let payload_len = Base16384::try_decode_len(n + padding.is_some() as usize, padding).ok();
+    // A padded last chunk may have 4 characters, which is not a full chunk.
+    let remainder_len = match (payload_len, padding.and_then(codec::last_chunk)) {
+        (Some(_), Some((_, last_chunk_size))) => last_chunk_size - 1,
+        _ => n % 4,
+    };
+    let mut remainder = [(0usize, 0u16); 4];
+    for (j, c) in remainder[..remainder_len].iter_mut().enumerate() {
+        *c = last[(n - remainder_len + j) % 4];
+    }
+    let remainder = &remainder[..remainder_len];
+
+    match (payload_len, padding.and_then(codec::last_chunk)) {
+        (None, _) => problems.push(Problem {
+            kind: ProblemKind::InvalidLength,
+            index: end,
+        }),
+        (Some(_), Some((padding_size, _))) => {
+            let mut chunk = [0u16; 4];
+            for (c, &(_, r)) in chunk.iter_mut().zip(remainder) {
+                *c = r;
+            }
+            let mut buf = [0u8; 7];
+            // A remainder with invalid characters is already reported.
+            let decoded = Base16384::decode_remainder(&chunk[..remainder_len], &mut buf, 7);
+            if let Ok(decoded) = decoded {
+                if padding_size == 0 || decoded[padding_size..].iter().any(|&b| b != 0) {
+                    problems.push(Problem {
+                        kind: ProblemKind::NonCanonical,
+                        index: remainder.last().map_or(end, |&(i, _)| i),
+                    });
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Report {
+        chunks: (n - remainder_len) / 4,
+        remainder_len,
+        padding,
+        payload_len,
+        problems,
+    }
+}
@@ -0,0 +1,185 @@
+//! Lazy iterator adapters.
+//!
+//! See [`Base16384::encode_iter`] and [`Base16384::decode_iter`].
+
+use core::iter::FusedIterator;
+
+use crate::error::Base16384DecodeError;
+use crate::state::DecoderState;
+use crate::utils::Scratch;
+use crate::Base16384;
+
+/// An iterator encoding bytes as Base16384 code units.
+///
+/// Created by [`Base16384::encode_iter`].
+#[derive(Debug, Clone)]
+pub struct EncodeIter<I> {
+    inner: I,
+    buf: [u16; 5],
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = u8>> EncodeIter<I> {
+    pub(crate) fn new(inner: I) -> Self {
+        Self {
+            inner,
+            buf: [0; 5],
+            pos: 0,
+            len: 0,
+            done: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for EncodeIter<I> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        if self.pos == self.len {
+            if self.done {
+                return None;
+            }
+            let mut chunk = Scratch::new([0u8; 7]);
+            let mut n = 0;
+            while n < 7 {
+                match self.inner.next() {
+                    Some(b) => chunk[n] = b,
+                    None => {
+                        self.done = true;
+                        break;
+                    }
+                }
+                n += 1;
+            }
+
+            let mut tmp = Scratch::new([0u16; 4]);
+            self.len = match n {
+                0 => return None,
+                7 => {
+                    self.buf[..4].copy_from_slice(Base16384::encode_chunk(&chunk, &mut tmp));
+                    4
+                }
+                n => {
+                    let encoded = Base16384::encode_remainder(&chunk[..n], &mut tmp);
+                    self.buf[..encoded.len()].copy_from_slice(encoded);
+                    self.buf[encoded.len()] = Base16384::PADDING_OFFSET | n as u16;
+                    encoded.len() + 1
+                }
+            };
+            self.pos = 0;
+        }
+        let c = self.buf[self.pos];
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.len - self.pos;
+        if self.done {
+            return (pending, Some(pending));
+        }
+        let (lower, upper) = self.inner.size_hint();
+        (
+            pending + Base16384::encode_len(lower),
+            upper.map(|upper| pending + Base16384::encode_len(upper)),
+        )
+    }
+}
+
+impl<I: ExactSizeIterator<Item = u8>> ExactSizeIterator for EncodeIter<I> {}
+
+impl<I: Iterator<Item = u8>> FusedIterator for EncodeIter<I> {}
+
+/// Wipes the buffered code units.
+#[cfg(feature = "zeroize")]
+impl<I> Drop for EncodeIter<I> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.buf);
+    }
+}
+
+/// An iterator decoding Base16384 code units into bytes.
+///
+/// Created by [`Base16384::decode_iter`]. It stops after yielding the first error.
+#[derive(Debug, Clone)]
+pub struct DecodeIter<I> {
+    inner: I,
+    state: DecoderState,
+    buf: [u8; 7],
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = u16>> DecodeIter<I> {
+    pub(crate) fn new(inner: I) -> Self {
+        Self {
+            inner,
+            state: DecoderState::new(),
+            buf: [0; 7],
+            pos: 0,
+            len: 0,
+            done: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u16>> Iterator for DecodeIter<I> {
+    type Item = Result<u8, Base16384DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos == self.len {
+            if self.done {
+                return None;
+            }
+            let result = match self.inner.next() {
+                Some(c) => self.state.update(&[c], &mut self.buf).map(|(_, n)| n),
+                None => {
+                    self.done = true;
+                    self.state.finalize(&mut self.buf)
+                }
+            };
+            match result {
+                Ok(n) => {
+                    self.pos = 0;
+                    self.len = n;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        let b = self.buf[self.pos];
+        self.pos += 1;
+        Some(Ok(b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.len - self.pos;
+        if self.done {
+            return (pending, Some(pending));
+        }
+        // Up to 4 code units may be buffered in the state.
+        let upper = self.inner.size_hint().1;
+        let upper = upper
+            .and_then(|upper| upper.checked_add(4))
+            .map(|upper| upper / 4 * 7);
+        (
+            pending,
+            upper.and_then(|upper| upper.checked_add(pending + 1)),
+        )
+    }
+}
+
+impl<I: Iterator<Item = u16>> FusedIterator for DecodeIter<I> {}
+
+/// Wipes the buffered bytes.
+#[cfg(feature = "zeroize")]
+impl<I> Drop for DecodeIter<I> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.buf);
+    }
+}
@@ -0,0 +1,141 @@
+//! Lenient decoding, skipping whitespace and separators.
+//!
+//! See [`Base16384::decode_lenient`] and [`Base16384Utf8::decode_lenient`].
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::error::Base16384DecodeError;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::state::DecoderState;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::utils;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::utils::Scratch;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::{Base16384, Base16384Utf8};
+
+/// The code points skipped by lenient decoding by default: ASCII whitespace, the
+/// ideographic space (U+3000), zero-width characters and the byte order mark.
+pub const DEFAULT_IGNORE: &[char] = &[
+    ' ', '\t', '\n', '\r', '\x0C', '\u{3000}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}',
+    '\u{FEFF}',
+];
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+impl Base16384 {
+    /// Decodes the given Base16384 data into a new allocated vector, skipping the code
+    /// points in `ignore` wherever they appear.
+    ///
+    /// Surrogate pairs are decoded before matching, so `ignore` may hold characters
+    /// outside of the BMP. The index of an invalid character is the index of the code
+    /// unit in the given data, including the skipped ones.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::lenient::DEFAULT_IGNORE;
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳\r\n廔萷　尀㴁\n".encode_utf16().collect::<Vec<_>>();
+    /// let decoded = Base16384::decode_lenient(&data, DEFAULT_IGNORE).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    pub fn decode_lenient(
+        data: &[u16],
+        ignore: &[char],
+    ) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        // Surrogate pairs are matched as a whole, and decoded as their code units.
+        let mut index = 0;
+        let units = core::char::decode_utf16(data.iter().cloned())
+            .map(|c| {
+                let len = c.as_ref().map_or(1, |c| c.len_utf16());
+                index += len;
+                (index - len, len, c)
+            })
+            .filter(|(_, _, c)| !matches!(c, Ok(c) if ignore.contains(c)))
+            .flat_map(|(i, len, _)| (i..i + len).map(|i| (i, data[i])));
+        decode_units(units, data.len() / 4 * 7 + 7)
+    }
+}
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+impl Base16384Utf8 {
+    /// Decodes the given utf8 data as Base16384 into a new allocated vector, skipping
+    /// the code points in `ignore` wherever they appear.
+    ///
+    /// The index of an invalid character is the byte index in the given data, including
+    /// the skipped characters.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::lenient::DEFAULT_IGNORE;
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let decoded = Base16384Utf8::decode_lenient("婌焳\r\n廔萷　尀㴁\n", DEFAULT_IGNORE);
+    /// assert_eq!(decoded.unwrap(), b"12345678");
+    /// ```
+    pub fn decode_lenient(
+        data: &str,
+        ignore: &[char],
+    ) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        let units = data
+            .char_indices()
+            .filter(|(_, c)| !ignore.contains(c))
+            .map(|(i, c)| (i, u16::try_from(c as u32).unwrap_or(u16::MAX)));
+        decode_units(units, data.len() / 12 * 7 + 7).map_err(|err| match err {
+            // Characters outside of the BMP are decoded as U+FFFF.
+            Base16384DecodeError::InvalidCharacter {
+                index,
+                padding_position,
+                ..
+            } => Base16384DecodeError::InvalidCharacter {
+                index,
+                value: data[index..].chars().next().map_or(0, |c| c as u32),
+                padding_position,
+            },
+            err => err,
+        })
+    }
+}
+
+/// Decodes the given code units along with their indexes in the input.
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+fn decode_units(
+    units: impl Iterator<Item = (usize, u16)>,
+    capacity: usize,
+) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+    let mut result = alloc::vec::Vec::with_capacity(capacity);
+    if let Err(err) = decode_units_into(units, &mut result) {
+        // The chunks decoded before the error are left in the vector.
+        utils::wipe_vec(&mut result);
+        return Err(err);
+    }
+    Ok(result)
+}
+
+/// Decodes the given code units along with their indexes in the input, appending the
+/// decoded bytes to `result`.
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+fn decode_units_into(
+    units: impl Iterator<Item = (usize, u16)>,
+    result: &mut alloc::vec::Vec<u8>,
+) -> Result<(), Base16384DecodeError> {
+    // The errors of the state are within the last chunk and the code unit after it.
+    let mut positions = [0usize; 8];
+    let locate = |positions: &[usize; 8], err: Base16384DecodeError| {
+        err.map_index(|index| positions[index % 8])
+    };
+
+    let mut state = DecoderState::new();
+    let mut buf = Scratch::new([0u8; 7]);
+    for (n, (i, c)) in units.enumerate() {
+        positions[n % 8] = i;
+        let (_, produced) = state
+            .update(&[c], &mut buf[..])
+            .map_err(|e| locate(&positions, e))?;
+        result.extend_from_slice(&buf[..produced]);
+    }
+    let produced = state
+        .finalize(&mut buf[..])
+        .map_err(|e| locate(&positions, e))?;
+    result.extend_from_slice(&buf[..produced]);
+    Ok(())
+}
@@ -19,6 +19,24 @@
 //! let decoded = Base16384::decode(&data).unwrap();
 //! assert_eq!(decoded, b"12345678");
 //! ```
+//!
+//! # Features
+//!
+//! - `std` (default) and `alloc` enable the allocating APIs.
+//! - `ct` checks the characters of each chunk, including the last one, without
+//!   branching on them, so that decoding valid data takes the same time whatever the
+//!   data is. This covers `decode` and `decode_to_slice` with their variants, the
+//!   readers and writers, the iterators and [`state::DecoderState`]. The length, the
+//!   padding and the error paths are not constant-time, and neither are `validate`,
+//!   `decode_lenient`, `decode_lossy`, `inspect` and the detection of payloads.
+//! - `zeroize` wipes the temporary chunks used while encoding and decoding, the
+//!   partial output of failed decodes into vectors, and the buffered chunk of
+//!   [`state::EncoderState`], [`state::DecoderState`] and the iterators. It also adds
+//!   `decode_zeroizing` for secret material. The internal buffers of the readers and
+//!   writers are not wiped.
+//!
+//! `ct` and `zeroize` are best-effort: the compiler may still copy data where it cannot
+//! be wiped.
 #![deny(missing_docs)]
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 
@@ -27,13 +45,45 @@ extern crate alloc;
 #[cfg(any(feature = "std", test))]
 extern crate std as alloc;
 
+pub mod auto;
+pub mod codec;
+pub mod config;
+#[cfg(feature = "ct")]
+mod ct;
+pub mod detect;
 pub mod error;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+pub mod inspect;
+pub mod iter;
+pub mod lenient;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+pub mod lossy;
+#[cfg(feature = "std")]
+pub mod read;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+pub mod scan;
+pub mod state;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+mod utf16;
 pub mod utf8;
 pub mod utils;
+pub mod wrap;
+#[cfg(feature = "std")]
+pub mod write;
+
+pub use codec::Codec;
+#[cfg(feature = "std")]
+pub use read::{Base16384Decoder, Base16384Utf8Decoder};
+pub use utf8::{Base16384Display, Base16384Utf8};
+#[cfg(feature = "std")]
+pub use write::{Base16384Encoder, Base16384Utf8Encoder};
 
-pub use utf8::Base16384Utf8;
+use core::mem::MaybeUninit;
 
-use error::Base16384DecodeError;
+use error::{Base16384DecodeError, Base16384EncodeError};
+use utils::Scratch;
 
 /// Base16384 encoding and decoding.
 pub struct Base16384;
@@ -70,6 +120,12 @@ impl Base16384 {
     /// "㴀㴁㴂㴃㴄㴅㴆" (U+3D00 to U+3D06).
     pub const PADDING_OFFSET: u16 = 0x3D00;
 
+    /// The UTF-16BE byte order mark.
+    pub const BOM_BE: [u8; 2] = [0xFE, 0xFF];
+
+    /// The UTF-16LE byte order mark.
+    pub const BOM_LE: [u8; 2] = [0xFF, 0xFE];
+
     /// Encodes the given data as Base16384 in a new allocated vector.
     ///
     /// # Examples
@@ -84,27 +140,42 @@ impl Base16384 {
     /// ```
     #[cfg(any(feature = "std", test, feature = "alloc"))]
     pub fn encode(data: &[u8]) -> alloc::vec::Vec<u16> {
-        let capacity = Self::encode_len(data.len());
-        let mut result = alloc::vec::Vec::with_capacity(capacity);
-
-        // SAFETY: `encode_chunk` guarantees that N is non-zero.
-        let (chunks, remainder) = unsafe { utils::slice_as_chunks(data) };
-        for chunk in chunks {
-            let mut buf = [0u16; 4];
-            result.extend_from_slice(Self::encode_chunk(chunk, &mut buf));
-        }
-        if !remainder.is_empty() {
-            let mut buf = [0u16; 4];
-            result.extend_from_slice(Self::encode_remainder(remainder, &mut buf));
-            result.push(0x3D00 | remainder.len() as u16)
-        }
+        let mut result = alloc::vec::Vec::new();
+        Self::encode_into_vec(data, &mut result);
         result
     }
 
+    /// Encodes the given data as Base16384, appending it to the given vector.
+    ///
+    /// Exactly [`Base16384::encode_len`] code units are reserved, so the vector can be
+    /// reused across calls without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let mut encoded = Vec::new();
+    /// for data in [b"12345678", b"87654321"] {
+    ///     encoded.clear();
+    ///     Base16384::encode_into_vec(data, &mut encoded);
+    ///     assert_eq!(encoded, Base16384::encode(data));
+    /// }
+    /// ```
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn encode_into_vec(data: &[u8], out: &mut alloc::vec::Vec<u16>) {
+        let len = out.len();
+        let additional = Self::encode_len(data.len());
+        out.reserve_exact(additional);
+        let written = Self::encode_to_uninit(data, out.spare_capacity_mut());
+        // SAFETY: `encode_to_uninit` initialized the first `written` code units.
+        unsafe { out.set_len(len + written) };
+    }
+
     /// Encodes the given data as Base16384 into the given buffer.
     ///
     /// # Panics
     /// Panics if the buffer is too small. Use [`Base16384::encode_len`] to get the required capacity.
+    /// See [`Base16384::try_encode_to_slice`] for a non-panicking variant.
     ///
     /// # Examples
     /// ```
@@ -118,31 +189,87 @@ impl Base16384 {
     /// assert_eq!(text, "婌焳廔萷尀㴁");
     /// ```
     pub fn encode_to_slice<'a>(data: &[u8], buf: &'a mut [u16]) -> &'a [u16] {
+        // SAFETY: `encode_to_uninit` only writes initialized code units.
+        let len = Self::encode_to_uninit(data, unsafe { utils::as_uninit_mut(buf) });
+        &buf[..len]
+    }
+
+    /// Encodes the given data into the given buffer, and returns the encoded length. The
+    /// buffer may be uninitialized, e.g. the spare capacity of a vector.
+    pub(crate) fn encode_to_uninit(data: &[u8], buf: &mut [MaybeUninit<u16>]) -> usize {
         let capacity = Self::encode_len(data.len());
         assert!(buf.len() >= capacity);
 
         // SAFETY: `encode_chunk` guarantees that N is non-zero.
         let (chunks, remainder) = unsafe { utils::slice_as_chunks(data) };
+        let mut tmp = Scratch::new([0u16; 4]);
         let mut i = 0;
         for chunk in chunks {
-            let mut tmp = [0u16; 4];
-            let encoded = Self::encode_chunk(chunk, &mut tmp);
-            buf[i..i + 4].copy_from_slice(encoded);
+            utils::write_slice(&mut buf[i..], Self::encode_chunk(chunk, &mut tmp));
             i += 4;
         }
         if !remainder.is_empty() {
-            let mut tmp = [0u16; 4];
             let encoded = Self::encode_remainder(remainder, &mut tmp);
-            buf[i..i + encoded.len()].copy_from_slice(encoded);
+            utils::write_slice(&mut buf[i..], encoded);
             i += encoded.len();
-            buf[i] = 0x3D00 | remainder.len() as u16;
+            buf[i] = MaybeUninit::new(0x3D00 | remainder.len() as u16);
             i += 1;
         }
-        &buf[..i]
+        i
+    }
+
+    /// Encodes the given data as Base16384 into the given buffer, or returns an error if
+    /// the buffer is too small.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    /// use base16384::error::Base16384EncodeError;
+    ///
+    /// let data = b"12345678";
+    /// let mut buf = [0u16; 6];
+    /// let encoded = Base16384::try_encode_to_slice(data, &mut buf).unwrap();
+    /// assert_eq!(String::from_utf16(encoded).unwrap(), "婌焳廔萷尀㴁");
+    ///
+    /// let mut buf = [0u16; 5];
+    /// let err = Base16384::try_encode_to_slice(data, &mut buf).unwrap_err();
+    /// assert_eq!(err, Base16384EncodeError::BufferTooSmall { needed: 6, available: 5 });
+    /// ```
+    pub fn try_encode_to_slice<'a>(
+        data: &[u8],
+        buf: &'a mut [u16],
+    ) -> Result<&'a [u16], Base16384EncodeError> {
+        let needed = Self::encode_len(data.len());
+        if buf.len() < needed {
+            return Err(Base16384EncodeError::BufferTooSmall {
+                needed,
+                available: buf.len(),
+            });
+        }
+        Ok(Self::encode_to_slice(data, buf))
+    }
+
+    /// Encodes the given bytes as Base16384 lazily.
+    ///
+    /// If the given iterator is an [`ExactSizeIterator`], so is the returned one.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let data = b"12345678".iter().cloned();
+    /// let encoded = Base16384::encode_iter(data);
+    /// assert_eq!(encoded.len(), 6);
+    ///
+    /// let text = char::decode_utf16(encoded).collect::<Result<String, _>>();
+    /// assert_eq!(text.unwrap(), "婌焳廔萷尀㴁");
+    /// ```
+    pub fn encode_iter<I: IntoIterator<Item = u8>>(data: I) -> iter::EncodeIter<I::IntoIter> {
+        iter::EncodeIter::new(data.into_iter())
     }
 
     #[inline]
-    fn encode_chunk<'a>(chunk: &[u8; 7], buf: &'a mut [u16; 4]) -> &'a [u16; 4] {
+    pub(crate) fn encode_chunk<'a>(chunk: &[u8; 7], buf: &'a mut [u16; 4]) -> &'a [u16; 4] {
         let b0_hi = chunk[0] as u16;
         let b0_lo = chunk[1] as u16;
         buf[0] = Self::START + ((b0_hi << 6) | (b0_lo >> 2));
@@ -165,8 +292,8 @@ impl Base16384 {
     }
 
     #[inline]
-    fn encode_remainder<'a>(remainder: &[u8], buf: &'a mut [u16; 4]) -> &'a [u16] {
-        let mut chunk = [0u8; 7];
+    pub(crate) fn encode_remainder<'a>(remainder: &[u8], buf: &'a mut [u16; 4]) -> &'a [u16] {
+        let mut chunk = Scratch::new([0u8; 7]);
         chunk[..remainder.len()].copy_from_slice(remainder);
         Self::encode_chunk(&chunk, buf);
         &buf[..remainder.len() / 2 + 1]
@@ -175,9 +302,9 @@ impl Base16384 {
     /// Returns the minimum number of bytes needed to decode the given number of u16s.
     /// The given offset is the padding code point of the last chunk (if exists).
     ///
-    /// # Panics
-    /// Panics if the given offset is out of the Base16384 padding code points range
-    /// (see [`Base16384::PADDING_OFFSET`]).
+    /// The result is meaningless if the given offset is out of the Base16384 padding code
+    /// points range (see [`Base16384::PADDING_OFFSET`]), or if `data_len` is too short
+    /// for the padding. See [`Base16384::try_decode_len`] to check them.
     ///
     /// # Examples
     /// ```
@@ -187,25 +314,61 @@ impl Base16384 {
     /// let padding = Base16384::padding(data.last().cloned().unwrap());
     /// let len = Base16384::decode_len(data.len(), padding);
     /// assert_eq!(len, 8);
+    ///
+    /// // Untrusted lengths are validated by `try_decode_len` instead.
+    /// assert_eq!(Base16384::try_decode_len(data.len(), padding), Ok(8));
+    /// assert!(Base16384::try_decode_len(data.len() - 1, padding).is_err());
     /// ```
     #[inline]
-    pub fn decode_len(mut data_len: usize, padding: Option<u16>) -> usize {
-        let r = if let Some(offset) = padding {
-            let offset = offset - Self::PADDING_OFFSET;
-            assert!((0..7).contains(&offset));
-            match offset {
-                0 => data_len -= 1,
-                1 => data_len -= 2,
-                2 | 3 => data_len -= 3,
-                4 | 5 => data_len -= 4,
-                6 => data_len -= 5,
-                _ => unreachable!(),
-            };
-            offset
-        } else {
-            0
+    pub fn decode_len(data_len: usize, padding: Option<u16>) -> usize {
+        let (padding_size, last_chunk_size) = padding.and_then(codec::last_chunk).unwrap_or((0, 0));
+        (data_len.saturating_sub(last_chunk_size) / 4)
+            .saturating_mul(7)
+            .saturating_add(padding_size)
+    }
+
+    /// Returns the number of bytes needed to decode the given number of u16s, or an error
+    /// if no valid Base16384 data has the given length and padding code point.
+    ///
+    /// This is the checked counterpart of [`Base16384::decode_len`].
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// assert_eq!(Base16384::try_decode_len(6, Some(0x3d01)), Ok(8));
+    /// assert!(Base16384::try_decode_len(6, Some(0x3d07)).is_err());
+    /// assert!(Base16384::try_decode_len(1, Some(0x3d01)).is_err());
+    /// assert!(Base16384::try_decode_len(6, None).is_err());
+    /// ```
+    #[inline]
+    pub fn try_decode_len(
+        data_len: usize,
+        padding: Option<u16>,
+    ) -> Result<usize, Base16384DecodeError> {
+        let (padding_size, last_chunk_size) = match padding {
+            Some(padding) => {
+                codec::last_chunk(padding).ok_or(Base16384DecodeError::InvalidPadding {
+                    index: data_len.saturating_sub(1),
+                    value: padding,
+                })?
+            }
+            None => (0, 0),
         };
-        data_len / 4 * 7 + r as usize
+        match data_len.checked_sub(last_chunk_size) {
+            // No data that fits in memory is long enough to overflow.
+            Some(data_len) if data_len % 4 == 0 => (data_len / 4)
+                .checked_mul(7)
+                .and_then(|len| len.checked_add(padding_size))
+                .ok_or(Base16384DecodeError::InvalidLength),
+            _ => Err(match padding {
+                Some(padding) => Base16384DecodeError::InvalidPadding {
+                    index: data_len.saturating_sub(1),
+                    value: padding,
+                },
+                None => Base16384DecodeError::InvalidLength,
+            }),
+        }
     }
 
     /// Gets the padding code point of the last chunk (if exists).
@@ -243,48 +406,69 @@ impl Base16384 {
     /// ```
     #[cfg(any(feature = "std", test, feature = "alloc"))]
     pub fn decode(data: &[u16]) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
-        let padding = data.last().cloned().and_then(Self::padding);
-        let capacity = Self::decode_len(data.len(), padding);
-        let mut result = alloc::vec::Vec::with_capacity(capacity);
-
-        let padding_size = padding.map(|padding| padding - Self::PADDING_OFFSET);
-        let last_chunk_size = padding_size.map(|padding_size| match padding_size {
-            0 => 1,
-            1 => 2,
-            2 | 3 => 3,
-            4 | 5 => 4,
-            6 => 5,
-            _ => unreachable!(),
-        });
+        let mut result = alloc::vec::Vec::new();
+        Self::decode_into_vec(data, &mut result)?;
+        Ok(result)
+    }
 
-        let (data, remainder) = if let Some(last_chunk_size) = last_chunk_size {
-            let (data, remainder) = data.split_at(data.len() - last_chunk_size);
-            (data, &remainder[..remainder.len() - 1])
-        } else {
-            (data, &[][..])
-        };
-        if data.len() % 4 != 0 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
+    /// Decodes the given data like [`Base16384::decode`], into a vector that is wiped
+    /// when dropped. Use it for secret material, such as keys.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// let decoded = Base16384::decode_zeroizing(&data).unwrap();
+    /// assert_eq!(*decoded, b"12345678");
+    /// ```
+    #[cfg(all(feature = "zeroize", any(feature = "std", test, feature = "alloc")))]
+    pub fn decode_zeroizing(
+        data: &[u16],
+    ) -> Result<zeroize::Zeroizing<alloc::vec::Vec<u8>>, Base16384DecodeError> {
+        let mut result = zeroize::Zeroizing::new(alloc::vec::Vec::new());
+        Self::decode_into_vec(data, &mut result)?;
+        Ok(result)
+    }
 
-        // SAFETY: `decode_chunk` guarantees that N is non-zero,
-        // and length of data is checked to be a multiple of N.
-        let chunks = unsafe { utils::slice_as_chunks_exact(data) };
-        for chunk in chunks {
-            let mut buf = [0u8; 7];
-            result.extend_from_slice(Self::decode_chunk(chunk, &mut buf)?);
-        }
-        if let Some(padding_size) = padding_size {
-            let mut buf = [0u8; 7];
-            result.extend_from_slice(Self::decode_remainder(remainder, &mut buf, padding_size)?);
+    /// Decodes the given Base16384 data, appending it to the given vector.
+    ///
+    /// Exactly [`Base16384::decode_len`] bytes are reserved, so the vector can be
+    /// reused across calls without reallocating. On error, the vector is left as is.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// let mut decoded = b"abc".to_vec();
+    /// Base16384::decode_into_vec(&data, &mut decoded).unwrap();
+    /// assert_eq!(decoded, b"abc12345678");
+    /// ```
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn decode_into_vec(
+        data: &[u16],
+        out: &mut alloc::vec::Vec<u8>,
+    ) -> Result<(), Base16384DecodeError> {
+        let len = out.len();
+        let additional = Self::checked_decode_len(data)?;
+        out.reserve_exact(additional);
+        match Self::decode_to_uninit(data, out.spare_capacity_mut()) {
+            // SAFETY: `decode_to_uninit` initialized the first `written` bytes.
+            Ok(written) => unsafe { out.set_len(len + written) },
+            Err(err) => {
+                // The chunks decoded before the error are left in the spare capacity.
+                utils::wipe(&mut out.spare_capacity_mut()[..additional]);
+                return Err(err);
+            }
         }
-        Ok(result)
+        Ok(())
     }
 
     /// Decodes the given Base16384 data into the given buffer.
     ///
-    /// # Panics
-    /// Panics if the buffer is too small. Use [`Base16384::decode_len`] to get the required capacity.
+    /// Returns [`Base16384DecodeError::BufferTooSmall`] if the buffer is too small. Use
+    /// [`Base16384::decode_len`] to get the required capacity.
     ///
     /// # Examples
     /// ```
@@ -299,67 +483,254 @@ impl Base16384 {
         data: &[u16],
         buf: &'a mut [u8],
     ) -> Result<&'a [u8], Base16384DecodeError> {
-        let padding = data.last().cloned().and_then(Self::padding);
-        let capacity = Self::decode_len(data.len(), padding);
-        assert!(buf.len() >= capacity);
-
-        let padding_size = padding.map(|padding| padding - Self::PADDING_OFFSET);
-        let last_chunk_size = padding_size.map(|padding_size| match padding_size {
-            0 => 1,
-            1 => 2,
-            2 | 3 => 3,
-            4 | 5 => 4,
-            6 => 5,
-            _ => unreachable!(),
-        });
+        // SAFETY: `decode_to_uninit` only writes initialized bytes.
+        let len = Self::decode_to_uninit(data, unsafe { utils::as_uninit_mut(buf) })?;
+        Ok(&buf[..len])
+    }
 
-        let (data, remainder) = if let Some(last_chunk_size) = last_chunk_size {
-            let (data, remainder) = data.split_at(data.len() - last_chunk_size);
-            (data, &remainder[..remainder.len() - 1])
-        } else {
-            (data, &[][..])
-        };
-        if data.len() % 4 != 0 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
+    /// Decodes the given data into the given buffer, and returns the decoded length. The
+    /// buffer may be uninitialized, e.g. the spare capacity of a vector.
+    pub(crate) fn decode_to_uninit(
+        data: &[u16],
+        buf: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, Base16384DecodeError> {
+        let padding = data.last().cloned().and_then(Self::padding);
+        let capacity = Self::checked_decode_len(data)?;
+        codec::check_buffer(capacity, buf.len())?;
+        // Errors are located in the whole data, since chunks hold no position.
+        let first_error = |err| Self::first_error(data, padding).unwrap_or(err);
+        let (chunks, remainder, padding_size) = codec::split_last_chunk(data, padding, 1)?;
 
         // SAFETY: `decode_chunk` guarantees that N is non-zero,
         // and length of data is checked to be a multiple of N.
-        let chunks = unsafe { utils::slice_as_chunks_exact(data) };
+        let chunks = unsafe { utils::slice_as_chunks_exact(chunks) };
+        let mut tmp = Scratch::new([0u8; 7]);
         let mut i = 0;
         for chunk in chunks {
-            let mut tmp = [0u8; 7];
-            let decoded = Self::decode_chunk(chunk, &mut tmp)?;
-            buf[i..i + 7].copy_from_slice(decoded);
+            let decoded = Self::decode_chunk(chunk, &mut tmp).map_err(first_error)?;
+            utils::write_slice(&mut buf[i..], decoded);
             i += 7;
         }
-        if let Some(padding_size) = padding_size {
-            let mut tmp = [0u8; 7];
-            let decoded = Self::decode_remainder(remainder, &mut tmp, padding_size)?;
-            buf[i..i + decoded.len()].copy_from_slice(decoded);
+        if padding.is_some() {
+            let decoded =
+                Self::decode_remainder(remainder, &mut tmp, padding_size).map_err(first_error)?;
+            utils::write_slice(&mut buf[i..], decoded);
             i += decoded.len();
         }
-        Ok(&buf[..i])
+        Ok(i)
+    }
+
+    /// Decodes the given Base16384 data into the given buffer, or returns an error if the
+    /// buffer is too small.
+    ///
+    /// This is the same as [`Base16384::decode_to_slice`], which checks the size of the
+    /// buffer as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use base16384::Base16384;
+    /// use base16384::error::Base16384DecodeError;
+    ///
+    /// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// let mut buf = [0u8; 8];
+    /// let decoded = Base16384::try_decode_to_slice(&data, &mut buf).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    ///
+    /// let mut buf = [0u8; 7];
+    /// let err = Base16384::try_decode_to_slice(&data, &mut buf).unwrap_err();
+    /// assert_eq!(err, Base16384DecodeError::BufferTooSmall { needed: 8, available: 7 });
+    /// ```
+    #[deprecated(note = "use `decode_to_slice`, which returns the same errors")]
+    pub fn try_decode_to_slice<'a>(
+        data: &[u16],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        Self::decode_to_slice(data, buf)
+    }
+
+    /// Checks the given Base16384 data without decoding it, and returns the number of
+    /// bytes it decodes to.
+    ///
+    /// This accepts exactly the data accepted by [`Base16384::decode`], without needing an
+    /// output buffer. The index of an invalid character is its index in the data.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    /// use base16384::error::Base16384DecodeError;
+    ///
+    /// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// assert_eq!(Base16384::validate(&data), Ok(8));
+    ///
+    /// let data = "婌焳廔萷x㴁".encode_utf16().collect::<Vec<_>>();
+    /// let err = Base16384::validate(&data).unwrap_err();
+    /// assert_eq!(err.index(), Some(4));
+    /// ```
+    pub fn validate(data: &[u16]) -> Result<usize, Base16384DecodeError> {
+        let padding = data.last().cloned().and_then(Self::padding);
+        match Self::first_error(data, padding) {
+            Some(err) => Err(err),
+            None => Self::try_decode_len(data.len(), padding),
+        }
+    }
+
+    /// Decodes the given Base16384 code units lazily.
+    ///
+    /// The returned iterator stops after yielding the first error.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳廔萷尀㴁".encode_utf16();
+    /// let decoded = Base16384::decode_iter(data).collect::<Result<Vec<_>, _>>();
+    /// assert_eq!(decoded.unwrap(), b"12345678");
+    /// ```
+    pub fn decode_iter<I: IntoIterator<Item = u16>>(data: I) -> iter::DecodeIter<I::IntoIter> {
+        iter::DecodeIter::new(data.into_iter())
+    }
+
+    /// Encodes the given data as Base16384 [`char`]s lazily.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_chars(b"12345678");
+    /// assert_eq!(encoded.len(), 6);
+    /// assert_eq!(encoded.collect::<String>(), "婌焳廔萷尀㴁");
+    /// ```
+    pub fn encode_chars(data: &[u8]) -> impl ExactSizeIterator<Item = char> + '_ {
+        Self::encode_iter(data.iter().cloned()).map(Self::unit_to_char)
+    }
+
+    /// Encodes the given data as Base16384 [`char`]s into the given buffer.
+    ///
+    /// # Panics
+    /// Panics if the buffer is too small. Use [`Base16384::encode_len`] to get the required capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let data = b"12345678";
+    /// let mut buf = ['\0'; 6];
+    /// let encoded = Base16384::encode_to_char_slice(data, &mut buf);
+    ///
+    /// assert_eq!(encoded.iter().collect::<String>(), "婌焳廔萷尀㴁");
+    /// ```
+    pub fn encode_to_char_slice<'a>(data: &[u8], buf: &'a mut [char]) -> &'a [char] {
+        let capacity = Self::encode_len(data.len());
+        assert!(buf.len() >= capacity, "buffer is too small");
+
+        for (c, out) in Self::encode_chars(data).zip(buf.iter_mut()) {
+            *out = c;
+        }
+        &buf[..capacity]
+    }
+
+    /// Decodes the given Base16384 [`char`]s lazily.
+    ///
+    /// Characters outside of the Base16384 alphabet are rejected, including those
+    /// outside of the Basic Multilingual Plane, which are reported with the value
+    /// U+FFFF. The returned iterator stops after yielding the first error.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let decoded = Base16384::decode_chars("婌焳廔萷尀㴁".chars());
+    /// assert_eq!(decoded.collect::<Result<Vec<_>, _>>().unwrap(), b"12345678");
+    ///
+    /// let decoded = Base16384::decode_chars("婌焳廔😀尀㴁".chars());
+    /// assert!(decoded.collect::<Result<Vec<_>, _>>().is_err());
+    /// ```
+    pub fn decode_chars<I: IntoIterator<Item = char>>(
+        data: I,
+    ) -> impl Iterator<Item = Result<u8, Base16384DecodeError>> {
+        // Characters outside of the BMP become U+FFFF, which is not in the alphabet.
+        Self::decode_iter(
+            data.into_iter()
+                .map(|c| u16::try_from(c as u32).unwrap_or(u16::MAX)),
+        )
     }
 
     #[inline]
-    fn is_valid_char(c: u16) -> bool {
-        (Self::START..Self::START + 0x3FFF).contains(&c)
+    fn unit_to_char(c: u16) -> char {
+        // SAFETY: Base16384 code units are neither surrogates nor out of range.
+        unsafe { char::from_u32_unchecked(c as u32) }
     }
 
     #[inline]
-    fn decode_chunk<'a>(
+    pub(crate) fn is_valid_char(c: u16) -> bool {
+        (Self::START..=Self::START + 0x3FFF).contains(&c)
+    }
+
+    /// Returns the error for the given code unit outside of the alphabet, at the given
+    /// index.
+    pub(crate) fn invalid_character(c: u16, index: usize) -> Base16384DecodeError {
+        match Self::padding(c) {
+            Some(_) => Base16384DecodeError::PaddingNotAtEnd { index },
+            None => Base16384DecodeError::InvalidCharacter {
+                index,
+                value: c as u32,
+                padding_position: false,
+            },
+        }
+    }
+
+    /// Returns the number of bytes needed to decode the given data, or the first error in
+    /// it if it has an invalid length.
+    pub(crate) fn checked_decode_len(data: &[u16]) -> Result<usize, Base16384DecodeError> {
+        let padding = data.last().cloned().and_then(Self::padding);
+        Self::try_decode_len(data.len(), padding)
+            .map_err(|err| Self::first_error(data, padding).unwrap_or(err))
+    }
+
+    /// Returns the error for the first code unit outside of the alphabet in the given
+    /// data, other than the trailing padding code point, if any.
+    pub(crate) fn first_error(data: &[u16], padding: Option<u16>) -> Option<Base16384DecodeError> {
+        let chars = &data[..data.len() - padding.is_some() as usize];
+        let index = chars.iter().position(|&c| !Self::is_valid_char(c))?;
+        Some(Self::invalid_character(chars[index], index).at_end(data.len(), 1))
+    }
+
+    /// Returns the error for the first invalid character of the given chunk.
+    #[cold]
+    fn chunk_error(chunk: &[u16; 4]) -> Base16384DecodeError {
+        let index = chunk
+            .iter()
+            .position(|&c| !Self::is_valid_char(c))
+            .unwrap_or(0);
+        Self::invalid_character(chunk[index], index)
+    }
+
+    /// Decodes a chunk. The index of an invalid character is the index in the chunk.
+    #[inline]
+    pub(crate) fn decode_chunk<'a>(
         chunk: &[u16; 4],
         buf: &'a mut [u8; 7],
     ) -> Result<&'a [u8; 7], Base16384DecodeError> {
-        if let Some(index) = chunk.iter().position(|&c| !Self::is_valid_char(c)) {
-            return Err(Base16384DecodeError::InvalidCharacter { index });
+        let mut offsets = Scratch::new([0u16; 4]);
+        #[cfg(not(feature = "ct"))]
+        match chunk.iter().all(|&c| Self::is_valid_char(c)) {
+            true => *offsets = chunk.map(|c| c - Self::START),
+            false => return Err(Self::chunk_error(chunk)),
+        }
+        // The whole chunk is checked at once, without branching on each character.
+        #[cfg(feature = "ct")]
+        if ct::unit_offsets(chunk, &mut offsets) != 0 {
+            return Err(Self::chunk_error(chunk));
         }
-        let b0 = chunk[0] - Self::START;
-        let b1 = chunk[1] - Self::START;
-        let b2 = chunk[2] - Self::START;
-        let b3 = chunk[3] - Self::START;
+        Ok(Self::unpack(&offsets, buf))
+    }
 
+    /// Unpacks the 14 bits of each of the given offsets from [`Base16384::START`] into 7
+    /// bytes.
+    #[inline]
+    pub(crate) fn unpack<'a>(offsets: &[u16; 4], buf: &'a mut [u8; 7]) -> &'a [u8; 7] {
+        let [b0, b1, b2, b3] = *offsets;
         buf[0] = (b0 >> 6) as u8;
         buf[1] = ((b0 & 0x3F) << 2 | (b1 >> 12)) as u8;
         buf[2] = (b1 >> 4) as u8;
@@ -367,19 +738,42 @@ impl Base16384 {
         buf[4] = (b2 >> 2) as u8;
         buf[5] = ((b2 & 0x03) << 6 | (b3 >> 8)) as u8;
         buf[6] = b3 as u8;
-
-        Ok(buf)
+        buf
     }
 
     #[inline]
-    fn decode_remainder<'a>(
+    pub(crate) fn decode_remainder<'a>(
         remainder: &[u16],
         buf: &'a mut [u8; 7],
-        padding_size: u16,
+        padding_size: usize,
     ) -> Result<&'a [u8], Base16384DecodeError> {
-        let mut chunk = [Self::START; 4];
+        let mut chunk = Scratch::new([Self::START; 4]);
         chunk[..remainder.len()].copy_from_slice(remainder);
         Self::decode_chunk(&chunk, buf)?;
-        Ok(&buf[..padding_size as usize])
+        Ok(&buf[..padding_size])
+    }
+
+    /// Decodes the last chunk of a stream, which is either empty, a full chunk, or a
+    /// partial chunk followed by the padding code point.
+    pub(crate) fn decode_last_chunk<'a>(
+        last: &[u16],
+        buf: &'a mut [u8; 7],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        let padding = last.last().cloned().and_then(Self::padding);
+        // Invalid characters are only looked for on errors, and take precedence.
+        let first_error = |err| Self::first_error(last, padding).unwrap_or(err);
+        match padding {
+            Some(padding) => {
+                let padding_size =
+                    Self::try_decode_len(last.len(), Some(padding)).map_err(first_error)?;
+                Self::decode_remainder(&last[..last.len() - 1], buf, padding_size)
+                    .map_err(first_error)
+            }
+            None => match last.try_into() {
+                Ok(chunk) => Ok(&Self::decode_chunk(chunk, buf).map_err(first_error)?[..]),
+                Err(_) if last.is_empty() => Ok(&buf[..0]),
+                Err(_) => Err(first_error(Base16384DecodeError::InvalidLength)),
+            },
+        }
     }
 }
@@ -0,0 +1,205 @@
+//! Best-effort decoding of damaged data.
+//!
+//! See [`Base16384::decode_lossy`] and [`Base16384Utf8::decode_lossy`].
+
+use core::ops::Range;
+
+use crate::error::Base16384DecodeError;
+use crate::utils::Scratch;
+use crate::{Base16384, Base16384Utf8};
+
+/// The result of lossy decoding.
+#[derive(Debug, PartialEq)]
+pub struct LossyOutput {
+    /// The decoded bytes, with the substitute byte where the data is damaged.
+    pub data: alloc::vec::Vec<u8>,
+    /// The errors found, in the order of their positions.
+    pub errors: alloc::vec::Vec<LossyError>,
+}
+
+impl LossyOutput {
+    /// Returns whether the data was decoded without errors.
+    pub fn is_lossless(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// An error found by lossy decoding.
+#[derive(Debug, PartialEq)]
+pub struct LossyError {
+    /// The error, with its index in the input.
+    pub error: Base16384DecodeError,
+    /// The range of the output bytes affected by the error.
+    pub range: Range<usize>,
+}
+
+impl Base16384 {
+    /// Decodes the given Base16384 data into a new allocated vector, recovering as much
+    /// as possible from damaged data.
+    ///
+    /// Instead of stopping at the first error, every invalid character is decoded to
+    /// `substitute` for the output byte made only of its bits. The 1 or 2 bytes it
+    /// shares with its neighbours keep their bits, with zero bits in place of its own;
+    /// [`LossyError::range`] spans all of them. If the length of the data does not
+    /// match the padding code point, the last partial chunk is decoded to as many bytes
+    /// as it fully covers.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::error::Base16384DecodeError;
+    /// use base16384::Base16384;
+    ///
+    /// let data = "婌焳x萷尀㴁".encode_utf16().collect::<Vec<_>>();
+    /// let output = Base16384::decode_lossy(&data, b'?');
+    /// // '4' and '6' share bits with the invalid character.
+    /// assert_eq!(output.data, b"1230?678");
+    /// assert_eq!(output.errors.len(), 1);
+    /// assert_eq!(
+    ///     output.errors[0].error,
+    ///     Base16384DecodeError::InvalidCharacter {
+    ///         index: 2,
+    ///         value: 'x' as u32,
+    ///         padding_position: false,
+    ///     }
+    /// );
+    /// assert_eq!(output.errors[0].range, 3..6);
+    /// ```
+    pub fn decode_lossy(data: &[u16], substitute: u8) -> LossyOutput {
+        let padding = data.last().cloned().and_then(Self::padding);
+        let chars = &data[..data.len() - padding.is_some() as usize];
+        decode_units(
+            chars.iter().map(|&c| c as u32).enumerate(),
+            chars.len(),
+            chars.len(),
+            padding,
+            substitute,
+        )
+    }
+}
+
+impl Base16384Utf8 {
+    /// Decodes the given utf8 data as Base16384 into a new allocated vector, recovering
+    /// as much as possible from damaged data.
+    ///
+    /// See [`Base16384::decode_lossy`]. The index of an invalid character is the byte
+    /// index in the given data.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::error::Base16384DecodeError;
+    /// use base16384::Base16384Utf8;
+    ///
+    /// // The padding code point is missing.
+    /// let output = Base16384Utf8::decode_lossy("婌焳廔萷尀", 0);
+    /// assert_eq!(output.data, b"12345678");
+    /// assert_eq!(output.errors.len(), 1);
+    /// assert_eq!(output.errors[0].error, Base16384DecodeError::InvalidLength);
+    /// assert_eq!(output.errors[0].range, 7..8);
+    /// ```
+    pub fn decode_lossy(data: &str, substitute: u8) -> LossyOutput {
+        let padding = data.chars().next_back().and_then(|c| {
+            let c = u16::try_from(c as u32).ok()?;
+            Base16384::padding(c)
+        });
+        let chars = &data[..data.len() - padding.map_or(0, |_| 3)];
+        let units = chars.char_indices().map(|(i, c)| (i, c as u32));
+        decode_units(
+            units,
+            chars.chars().count(),
+            chars.len(),
+            padding,
+            substitute,
+        )
+    }
+}
+
+/// Decodes the given `n` code points other than the trailing padding code point, along
+/// with their indexes in the data. `end` is the index of the padding code point, or the
+/// length of the data if there is none.
+fn decode_units(
+    units: impl Iterator<Item = (usize, u32)>,
+    n: usize,
+    end: usize,
+    padding: Option<u16>,
+    substitute: u8,
+) -> LossyOutput {
+    let full_len = n / 4 * 7;
+    let valid_len = match padding {
+        Some(padding) => Base16384::try_decode_len(n + 1, Some(padding)).ok(),
+        None => Base16384::try_decode_len(n, None).ok(),
+    };
+    // Data of an invalid length is decoded as if the padding was missing.
+    let len = valid_len.unwrap_or(full_len + n % 4 * 14 / 8);
+
+    let mut data = alloc::vec::Vec::with_capacity(len);
+    let mut errors = alloc::vec::Vec::new();
+    let mut chunk = Scratch::new([Base16384::START; 4]);
+    let mut invalid = [false; 4];
+    let mut buf = Scratch::new([0u8; 7]);
+    let fallback = [substitute; 7];
+    for (k, (i, c)) in units.enumerate() {
+        let error = match u16::try_from(c) {
+            Ok(c) if Base16384::is_valid_char(c) => {
+                chunk[k % 4] = c;
+                None
+            }
+            Ok(c) if Base16384::padding(c).is_some() => {
+                Some(Base16384DecodeError::PaddingNotAtEnd { index: i })
+            }
+            _ => Some(Base16384DecodeError::InvalidCharacter {
+                index: i,
+                value: c,
+                padding_position: padding.is_none() && k + 1 == n,
+            }),
+        };
+        if let Some(error) = error {
+            let range = range(k, len);
+            errors.push(LossyError { error, range });
+            invalid[k % 4] = true;
+        }
+        if k % 4 == 3 || k + 1 == n {
+            // Invalid characters are left as `START`, so the chunk always decodes, with
+            // zero bits in their place.
+            let decoded = match Base16384::decode_chunk(&chunk, &mut buf) {
+                Ok(decoded) => &decoded[..],
+                Err(_) => &fallback[..],
+            };
+            let produced = (len - data.len()).min(7);
+            data.extend_from_slice(&decoded[..produced]);
+            for (j, _) in invalid.iter().enumerate().filter(|(_, &invalid)| invalid) {
+                data[covered(k / 4 * 4 + j, len)].fill(substitute);
+            }
+            *chunk = [Base16384::START; 4];
+            invalid = [false; 4];
+        }
+    }
+    if valid_len.is_none() {
+        errors.push(LossyError {
+            error: match padding {
+                Some(value) => Base16384DecodeError::InvalidPadding { index: end, value },
+                None => Base16384DecodeError::InvalidLength,
+            },
+            range: full_len..len,
+        });
+    }
+    LossyOutput { data, errors }
+}
+
+/// Returns the range of the output bytes holding the bits of the `k`-th character,
+/// clipped to the output length.
+fn range(k: usize, len: usize) -> Range<usize> {
+    // The k-th character of a chunk holds bits 14k to 14k + 14 of it.
+    let chunk_start = k / 4 * 7;
+    let start = chunk_start + k % 4 * 14 / 8;
+    let stop = chunk_start + (k % 4 * 14 + 14 + 7) / 8;
+    start.min(len)..stop.min(len)
+}
+
+/// Returns the range of the output bytes made only of the bits of the `k`-th character,
+/// clipped to the output length.
+fn covered(k: usize, len: usize) -> Range<usize> {
+    let chunk_start = k / 4 * 7;
+    let start = chunk_start + (k % 4 * 14 + 7) / 8;
+    let stop = chunk_start + (k % 4 * 14 + 14) / 8;
+    start.min(len)..stop.min(len)
+}
@@ -0,0 +1,371 @@
+//! Streaming adapters implementing [`std::io::Read`].
+
+use std::io::{self, Read};
+use std::marker::PhantomData;
+
+use crate::error::Base16384DecodeError;
+use crate::stream::Repr;
+use crate::{Base16384, Base16384Utf8};
+
+/// The size of the input buffer, in bytes.
+const BUF_SIZE: usize = 1024;
+
+/// Reads Base16384 in UTF-16BE from the inner reader, and decodes it on the fly.
+///
+/// A chunk of 4 code units is only decoded once the code unit after it is known,
+/// since it may be the partial last chunk if a padding code point follows.
+///
+/// Decoding errors are returned as [`io::Error`]s of kind [`InvalidData`], wrapping a
+/// [`Base16384DecodeError`]. The index of an invalid character is the index of the
+/// code unit in the whole stream.
+///
+/// [`InvalidData`]: io::ErrorKind::InvalidData
+///
+/// # Examples
+/// ```
+/// use std::io::Read;
+/// use base16384::Base16384Decoder;
+///
+/// let text = "婌焳廔萷尀㴁"
+///     .encode_utf16()
+///     .flat_map(u16::to_be_bytes)
+///     .collect::<Vec<_>>();
+/// let mut decoder = Base16384Decoder::new(&text[..]);
+/// let mut decoded = Vec::new();
+/// decoder.read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, b"12345678");
+/// ```
+pub type Base16384Decoder<R> = DecodingReader<R, Base16384>;
+
+/// Reads Base16384 in UTF-8 from the inner reader, and decodes it on the fly.
+///
+/// This is the UTF-8 counterpart of [`Base16384Decoder`]. A chunk of 12 bytes is only
+/// decoded once the character after it is known. The index of an invalid character
+/// is the byte index in the whole stream.
+///
+/// # Examples
+/// ```
+/// use std::io::Read;
+/// use base16384::Base16384Utf8Decoder;
+///
+/// let mut decoder = Base16384Utf8Decoder::new("婌焳廔萷尀㴁".as_bytes());
+/// let mut decoded = Vec::new();
+/// decoder.read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, b"12345678");
+/// ```
+pub type Base16384Utf8Decoder<R> = DecodingReader<R, Base16384Utf8>;
+
+/// Reads Base16384 from the inner reader, and decodes it on the fly.
+///
+/// Use it as [`Base16384Decoder`] for UTF-16BE, or as [`Base16384Utf8Decoder`] for
+/// UTF-8.
+pub struct DecodingReader<R: Read, C: Repr> {
+    inner: R,
+    buf: [u8; BUF_SIZE],
+    start: usize,
+    end: usize,
+    eof: bool,
+    out: [u8; 7],
+    out_start: usize,
+    out_end: usize,
+    position: usize,
+    finished: bool,
+    repr: PhantomData<C>,
+}
+
+impl<R: Read, C: Repr> DecodingReader<R, C> {
+    /// Creates a new decoder reading from the given reader.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: [0; BUF_SIZE],
+            start: 0,
+            end: 0,
+            eof: false,
+            out: [0; 7],
+            out_start: 0,
+            out_end: 0,
+            position: 0,
+            finished: false,
+            repr: PhantomData,
+        }
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader.
+    ///
+    /// Reading from the inner reader directly will corrupt the decoded output.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps the decoder, returning the inner reader.
+    ///
+    /// Any buffered input or output is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    #[inline]
+    fn available(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the next 4 characters form a full chunk.
+    #[inline]
+    fn chunk_ready(&self) -> bool {
+        self.available() >= C::CHUNK + C::CHAR && !C::is_padding(&self.buf[self.start + C::CHUNK..])
+    }
+
+    fn decode_chunk(&mut self, buf: &mut [u8; 7]) -> Result<(), Base16384DecodeError> {
+        let data = &self.buf[self.start..self.end];
+        C::decode_chunk(data, self.eof, buf).map_err(|e| e.offset(self.position))?;
+        self.start += C::CHUNK;
+        self.position += C::CHUNK / C::INDEX;
+        Ok(())
+    }
+
+    fn decode_tail(&mut self) -> Result<(), Base16384DecodeError> {
+        let n = self.available();
+        let mut out = [0u8; 7];
+        let decoded = C::decode_last_chunk(&self.buf[self.start..self.end], &mut out)
+            .map_err(|e| e.offset(self.position))?
+            .len();
+        self.out = out;
+        self.start = self.end;
+        self.position += n / C::INDEX;
+        self.out_start = 0;
+        self.out_end = decoded;
+        self.finished = true;
+        Ok(())
+    }
+
+    fn fill_buf(&mut self) -> io::Result<()> {
+        self.buf.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+        let n = self.inner.read(&mut self.buf[self.end..])?;
+        self.end += n;
+        self.eof = n == 0;
+        Ok(())
+    }
+}
+
+impl<R: Read, C: Repr> Read for DecodingReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if self.out_start < self.out_end {
+                let n = buf.len().min(self.out_end - self.out_start);
+                buf[..n].copy_from_slice(&self.out[self.out_start..self.out_start + n]);
+                self.out_start += n;
+                return Ok(n);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+
+            // Decode as many full chunks as possible directly into the output.
+            let mut n = 0;
+            while n + 7 <= buf.len() && self.chunk_ready() {
+                let out = (&mut buf[n..n + 7]).try_into().unwrap();
+                match self.decode_chunk(out) {
+                    Ok(()) => n += 7,
+                    Err(_) if n > 0 => break,
+                    Err(err) => return Err(invalid_data(err)),
+                }
+            }
+            if n > 0 {
+                return Ok(n);
+            }
+
+            if self.chunk_ready() {
+                let mut out = [0u8; 7];
+                self.decode_chunk(&mut out).map_err(invalid_data)?;
+                self.out = out;
+                self.out_start = 0;
+                self.out_end = 7;
+            } else if self.available() / C::INDEX > (C::CHUNK + C::CHAR) / C::INDEX {
+                // The padding code point is followed by more data.
+                let index = self.position + C::CHUNK / C::INDEX;
+                return Err(invalid_data(Base16384DecodeError::PaddingNotAtEnd {
+                    index,
+                }));
+            } else if self.eof {
+                self.decode_tail().map_err(invalid_data)?;
+            } else {
+                self.fill_buf()?;
+            }
+        }
+    }
+}
+
+/// Reads raw bytes from the inner reader, and encodes them as Base16384 in UTF-16BE
+/// on the fly.
+///
+/// This is the inverse of [`Base16384Decoder`]: reading from it yields encoded text,
+/// which is handy to feed an HTTP body or [`io::copy`].
+///
+/// # Examples
+/// ```
+/// use std::io::Read;
+/// use base16384::read::EncoderReader;
+///
+/// let mut encoder = EncoderReader::new(&b"12345678"[..]);
+/// let mut encoded = Vec::new();
+/// encoder.read_to_end(&mut encoded).unwrap();
+///
+/// let text = "婌焳廔萷尀㴁"
+///     .encode_utf16()
+///     .flat_map(u16::to_be_bytes)
+///     .collect::<Vec<_>>();
+/// assert_eq!(encoded, text);
+/// ```
+pub type EncoderReader<R> = EncodingReader<R, Base16384>;
+
+/// Reads raw bytes from the inner reader, and encodes them as Base16384 in UTF-8 on
+/// the fly.
+///
+/// This is the UTF-8 counterpart of [`EncoderReader`].
+///
+/// # Examples
+/// ```
+/// use std::io::Read;
+/// use base16384::read::Utf8EncoderReader;
+///
+/// let mut encoder = Utf8EncoderReader::new(&b"12345678"[..]);
+/// let mut encoded = String::new();
+/// encoder.read_to_string(&mut encoded).unwrap();
+/// assert_eq!(encoded, "婌焳廔萷尀㴁");
+/// ```
+pub type Utf8EncoderReader<R> = EncodingReader<R, Base16384Utf8>;
+
+/// Reads raw bytes from the inner reader, and encodes them as Base16384 on the fly.
+///
+/// Use it as [`EncoderReader`] for UTF-16BE, or as [`Utf8EncoderReader`] for UTF-8.
+pub struct EncodingReader<R: Read, C: Repr> {
+    inner: R,
+    buf: [u8; BUF_SIZE],
+    start: usize,
+    end: usize,
+    eof: bool,
+    out: [u8; 15],
+    out_start: usize,
+    out_end: usize,
+    finished: bool,
+    repr: PhantomData<C>,
+}
+
+impl<R: Read, C: Repr> EncodingReader<R, C> {
+    /// Creates a new encoder reading from the given reader.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: [0; BUF_SIZE],
+            start: 0,
+            end: 0,
+            eof: false,
+            out: [0; 15],
+            out_start: 0,
+            out_end: 0,
+            finished: false,
+            repr: PhantomData,
+        }
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader.
+    ///
+    /// Reading from the inner reader directly will corrupt the encoded output.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps the encoder, returning the inner reader.
+    ///
+    /// Any buffered input or output is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn encode_chunk(&mut self, out: &mut [u8]) {
+        let chunk = self.buf[self.start..self.start + 7].try_into().unwrap();
+        C::encode_chunk(chunk, out);
+        self.start += 7;
+    }
+
+    fn encode_tail(&mut self) {
+        let remainder = &self.buf[self.start..self.end];
+        self.out_end = match remainder.is_empty() {
+            true => 0,
+            false => C::encode_tail(remainder, &mut self.out),
+        };
+        self.start = self.end;
+        self.out_start = 0;
+        self.finished = true;
+    }
+
+    fn fill_buf(&mut self) -> io::Result<()> {
+        self.buf.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+        let n = self.inner.read(&mut self.buf[self.end..])?;
+        self.end += n;
+        self.eof = n == 0;
+        Ok(())
+    }
+}
+
+impl<R: Read, C: Repr> Read for EncodingReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if self.out_start < self.out_end {
+                let n = buf.len().min(self.out_end - self.out_start);
+                buf[..n].copy_from_slice(&self.out[self.out_start..self.out_start + n]);
+                self.out_start += n;
+                return Ok(n);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+
+            if self.end - self.start >= 7 {
+                // Encode as many full chunks as possible directly into the output.
+                let mut n = 0;
+                while n + C::CHUNK <= buf.len() && self.end - self.start >= 7 {
+                    self.encode_chunk(&mut buf[n..n + C::CHUNK]);
+                    n += C::CHUNK;
+                }
+                if n > 0 {
+                    return Ok(n);
+                }
+                let mut out = [0u8; 12];
+                self.encode_chunk(&mut out[..C::CHUNK]);
+                self.out[..C::CHUNK].copy_from_slice(&out[..C::CHUNK]);
+                self.out_start = 0;
+                self.out_end = C::CHUNK;
+            } else if self.eof {
+                self.encode_tail();
+            } else {
+                self.fill_buf()?;
+            }
+        }
+    }
+}
+
+pub(crate) fn invalid_data(err: Base16384DecodeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
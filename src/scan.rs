@@ -0,0 +1,150 @@
+//! Extraction of Base16384 payloads embedded in text.
+//!
+//! See [`Base16384Utf8::find_payloads`].
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::config::Config;
+use crate::detect::is_common;
+use crate::Base16384Utf8;
+
+/// A Base16384 payload found in text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The byte range of the payload in the text.
+    pub range: Range<usize>,
+    /// The decoded payload.
+    pub data: alloc::vec::Vec<u8>,
+}
+
+/// An iterator over the Base16384 payloads in text.
+///
+/// Created by [`Base16384Utf8::find_payloads`].
+#[derive(Debug, Clone)]
+pub struct Payloads<'a> {
+    text: &'a str,
+    pos: usize,
+    min_unpadded_chars: usize,
+}
+
+impl<'a> Payloads<'a> {
+    /// The default minimum number of characters of a payload without padding.
+    pub const DEFAULT_MIN_UNPADDED_CHARS: usize = 12;
+
+    /// Sets the minimum number of characters of a payload without padding.
+    ///
+    /// Short runs of alphabet characters are common in Chinese prose, so a payload
+    /// without the padding code point is only accepted if it is at least this long.
+    pub fn min_unpadded_chars(self, n: usize) -> Self {
+        Self {
+            min_unpadded_chars: n,
+            ..self
+        }
+    }
+
+    /// Returns the run of alphabet characters starting at the next one from `self.pos`,
+    /// including the padding code point after it.
+    fn next_run(&mut self) -> Option<(Range<usize>, bool)> {
+        let rest = &self.text[self.pos..];
+        let start = self.pos + rest.find(is_alphabet)?;
+        let rest = &self.text[start..];
+        let mut end = start + rest.find(|c| !is_alphabet(c)).unwrap_or(rest.len());
+        let padded = self.text[end..].starts_with(is_padding);
+        if padded {
+            end += 3;
+        }
+        self.pos = end;
+        Some((start..end, padded))
+    }
+
+    /// Returns whether the given run looks like a payload rather than prose.
+    fn is_payload(&self, range: &Range<usize>, padded: bool) -> bool {
+        let chars = (range.end - range.start) / 3;
+        if !padded && (chars < self.min_unpadded_chars || chars % 4 != 0) {
+            return false;
+        }
+        // Runs of the alphabet next to other ideographs are most likely prose.
+        let before = self.text[..range.start].chars().next_back();
+        let after = self.text[range.end..].chars().next();
+        if before.map_or(false, is_ideograph) || after.map_or(false, is_ideograph) {
+            return false;
+        }
+        // Few characters of Base16384 are common hanzi, but most of Chinese prose is.
+        // The count is smoothed like in `detect`, so that a single common character does
+        // not rule out a short payload.
+        let run = self.text[range.clone()].chars().filter(|&c| is_alphabet(c));
+        let common = run.filter(|&c| is_common(c as u16)).count();
+        common * 2 < chars + 2
+    }
+}
+
+impl Iterator for Payloads<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Span> {
+        loop {
+            let (range, padded) = self.next_run()?;
+            if !self.is_payload(&range, padded) {
+                continue;
+            }
+            let config = Config::new().with_strict(true);
+            if let Ok(data) = Base16384Utf8::decode_with_config(&self.text[range.clone()], config) {
+                return Some(Span { range, data });
+            }
+        }
+    }
+}
+
+impl FusedIterator for Payloads<'_> {}
+
+impl Base16384Utf8 {
+    /// Finds the Base16384 payloads embedded in the given text, and decodes them.
+    ///
+    /// A payload is a maximal run of alphabet characters (U+4E00 to U+8DFF), optionally
+    /// followed by a padding code point. Since the alphabet overlaps everyday hanzi,
+    /// runs are skipped if they are next to other CJK ideographs, if they are mostly
+    /// made of common hanzi, if they are short and not padded (see
+    /// [`Payloads::min_unpadded_chars`]), or if they are not the canonical encoding of
+    /// some data.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let text = "here is the file: 婌焳廔萷尀㴁 thanks";
+    /// let spans = Base16384Utf8::find_payloads(text).collect::<Vec<_>>();
+    /// assert_eq!(spans.len(), 1);
+    /// assert_eq!(&text[spans[0].range.clone()], "婌焳廔萷尀㴁");
+    /// assert_eq!(spans[0].data, b"12345678");
+    ///
+    /// // Chinese prose is not a payload.
+    /// let text = "我们需要一个新的方案来处理这个问题。";
+    /// assert_eq!(Base16384Utf8::find_payloads(text).count(), 0);
+    /// ```
+    pub fn find_payloads(text: &str) -> Payloads<'_> {
+        Payloads {
+            text,
+            pos: 0,
+            min_unpadded_chars: Payloads::DEFAULT_MIN_UNPADDED_CHARS,
+        }
+    }
+}
+
+fn is_alphabet(c: char) -> bool {
+    ('\u{4E00}'..='\u{8DFF}').contains(&c)
+}
+
+fn is_padding(c: char) -> bool {
+    ('\u{3D00}'..='\u{3D06}').contains(&c)
+}
+
+/// Returns whether the given character is a CJK ideograph other than the padding.
+fn is_ideograph(c: char) -> bool {
+    matches!(c,
+        '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{3FFFF}')
+        && !is_padding(c)
+}
@@ -0,0 +1,220 @@
+//! Incremental, allocation-free encoding and decoding.
+//!
+//! [`EncoderState`] and [`DecoderState`] are push-style state machines: feed them
+//! input as it arrives with `update`, and call `finalize` after the last input.
+//! They carry at most one partial chunk internally, and work without `alloc`.
+
+use crate::error::Base16384DecodeError;
+use crate::utils::Scratch;
+use crate::{codec, Base16384};
+
+/// Incremental Base16384 encoder.
+///
+/// # Examples
+/// ```
+/// use base16384::state::EncoderState;
+///
+/// let mut state = EncoderState::new();
+/// let mut buf = [0u16; 6];
+/// let (consumed, mut produced) = state.update(b"1234", &mut buf);
+/// assert_eq!((consumed, produced), (4, 0));
+///
+/// let (consumed, n) = state.update(b"5678", &mut buf);
+/// assert_eq!((consumed, n), (4, 4));
+/// produced += n;
+/// produced += state.finalize(&mut buf[produced..]);
+///
+/// let text = String::from_utf16(&buf[..produced]).unwrap();
+/// assert_eq!(text, "婌焳廔萷尀㴁");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EncoderState {
+    chunk: [u8; 7],
+    len: usize,
+}
+
+impl EncoderState {
+    /// Creates a new encoder state.
+    pub const fn new() -> Self {
+        Self {
+            chunk: [0; 7],
+            len: 0,
+        }
+    }
+
+    /// Encodes as much of `input` into `output` as possible.
+    ///
+    /// Returns the number of bytes consumed from `input` and the number of code units
+    /// written to `output`. Bytes that do not fill a chunk yet are buffered; a full
+    /// chunk stays buffered if `output` has no room for its 4 code units.
+    pub fn update(&mut self, input: &[u8], output: &mut [u16]) -> (usize, usize) {
+        let mut consumed = 0;
+        let mut produced = 0;
+        loop {
+            let n = (input.len() - consumed).min(7 - self.len);
+            self.chunk[self.len..self.len + n].copy_from_slice(&input[consumed..consumed + n]);
+            self.len += n;
+            consumed += n;
+            if self.len < 7 || output.len() - produced < 4 {
+                break;
+            }
+
+            let buf = (&mut output[produced..produced + 4]).try_into().unwrap();
+            Base16384::encode_chunk(&self.chunk, buf);
+            produced += 4;
+            self.len = 0;
+        }
+        (consumed, produced)
+    }
+
+    /// Encodes the buffered bytes and the padding code point into `output`, and resets
+    /// the state.
+    ///
+    /// Returns the number of code units written to `output`.
+    ///
+    /// # Panics
+    /// Panics if `output` is too small for the buffered chunk, which takes at most 5
+    /// code units.
+    pub fn finalize(&mut self, output: &mut [u16]) -> usize {
+        let mut tmp = Scratch::new([0u16; 4]);
+        let produced = match self.len {
+            0 => 0,
+            7 => {
+                assert!(output.len() >= 4, "buffer is too small");
+                output[..4].copy_from_slice(Base16384::encode_chunk(&self.chunk, &mut tmp));
+                4
+            }
+            len => {
+                let encoded = Base16384::encode_remainder(&self.chunk[..len], &mut tmp);
+                assert!(output.len() > encoded.len(), "buffer is too small");
+                output[..encoded.len()].copy_from_slice(encoded);
+                output[encoded.len()] = Base16384::PADDING_OFFSET | len as u16;
+                encoded.len() + 1
+            }
+        };
+        self.len = 0;
+        produced
+    }
+}
+
+/// Wipes the buffered chunk.
+#[cfg(feature = "zeroize")]
+impl Drop for EncoderState {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.chunk);
+    }
+}
+
+/// Incremental Base16384 decoder.
+///
+/// A chunk of 4 code units is only decoded once the code unit after it is known,
+/// since it may be the partial last chunk if a padding code point follows. The index
+/// of an invalid character is the index of the code unit in the whole input.
+///
+/// # Examples
+/// ```
+/// use base16384::state::DecoderState;
+///
+/// let data = "婌焳廔萷尀㴁".encode_utf16().collect::<Vec<_>>();
+/// let mut state = DecoderState::new();
+/// let mut buf = [0u8; 8];
+/// let (consumed, mut produced) = state.update(&data[..4], &mut buf).unwrap();
+/// assert_eq!((consumed, produced), (4, 0));
+///
+/// let (consumed, n) = state.update(&data[4..], &mut buf).unwrap();
+/// assert_eq!((consumed, n), (2, 7));
+/// produced += n;
+/// produced += state.finalize(&mut buf[produced..]).unwrap();
+/// assert_eq!(&buf[..produced], b"12345678");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DecoderState {
+    chunk: [u16; 4],
+    len: usize,
+    padding: Option<u16>,
+    position: usize,
+}
+
+impl DecoderState {
+    /// Creates a new decoder state.
+    pub const fn new() -> Self {
+        Self {
+            chunk: [0; 4],
+            len: 0,
+            padding: None,
+            position: 0,
+        }
+    }
+
+    /// Decodes as much of `input` into `output` as possible.
+    ///
+    /// Returns the number of code units consumed from `input` and the number of bytes
+    /// written to `output`. A full chunk stays buffered until the next code unit is
+    /// known, or if `output` has no room for its 7 bytes.
+    pub fn update(
+        &mut self,
+        input: &[u16],
+        output: &mut [u8],
+    ) -> Result<(usize, usize), Base16384DecodeError> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        while let Some(&c) = input.get(consumed) {
+            if self.padding.is_some() {
+                // The padding code point is followed by more data.
+                return Err(Base16384DecodeError::PaddingNotAtEnd {
+                    index: self.position + self.len,
+                });
+            }
+            if let Some(padding) = Base16384::padding(c) {
+                self.padding = Some(padding);
+                consumed += 1;
+                continue;
+            }
+            if self.len == 4 {
+                if output.len() - produced < 7 {
+                    break;
+                }
+                let buf = (&mut output[produced..produced + 7]).try_into().unwrap();
+                Base16384::decode_chunk(&self.chunk, buf).map_err(|e| e.offset(self.position))?;
+                produced += 7;
+                self.position += 4;
+                self.len = 0;
+            }
+            self.chunk[self.len] = c;
+            self.len += 1;
+            consumed += 1;
+        }
+        Ok((consumed, produced))
+    }
+
+    /// Decodes the buffered chunk into `output`, and resets the state.
+    ///
+    /// Returns the number of bytes written to `output`. If `output` is too small for the
+    /// buffered chunk, which takes at most 7 bytes, returns
+    /// [`Base16384DecodeError::BufferTooSmall`] and keeps the state.
+    pub fn finalize(&mut self, output: &mut [u8]) -> Result<usize, Base16384DecodeError> {
+        let mut last = Scratch::new([0u16; 5]);
+        last[..self.len].copy_from_slice(&self.chunk[..self.len]);
+        let mut n = self.len;
+        if let Some(padding) = self.padding {
+            last[n] = padding;
+            n += 1;
+        }
+
+        let mut buf = Scratch::new([0u8; 7]);
+        let decoded = Base16384::decode_last_chunk(&last[..n], &mut buf)
+            .map_err(|e| e.offset(self.position))?;
+        codec::check_buffer(decoded.len(), output.len())?;
+        output[..decoded.len()].copy_from_slice(decoded);
+        *self = Self::new();
+        Ok(decoded.len())
+    }
+}
+
+/// Wipes the buffered chunk.
+#[cfg(feature = "zeroize")]
+impl Drop for DecoderState {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.chunk);
+    }
+}
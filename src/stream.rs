@@ -0,0 +1,162 @@
+//! The byte representations of Base16384 streams, shared by the readers and writers.
+
+use crate::error::Base16384DecodeError;
+use crate::utils::Scratch;
+use crate::{Base16384, Base16384Utf8};
+
+/// A byte representation of Base16384 streams: UTF-16BE for [`Base16384`], and UTF-8
+/// for [`Base16384Utf8`].
+///
+/// The trait is public in a private module, so it cannot be named or implemented
+/// outside of the crate.
+pub trait Repr {
+    /// The number of bytes in a character.
+    const CHAR: usize;
+
+    /// The number of bytes in a full chunk of 4 characters.
+    const CHUNK: usize = Self::CHAR * 4;
+
+    /// The number of bytes per index of the stream: the code units of UTF-16, or the
+    /// bytes of UTF-8.
+    const INDEX: usize;
+
+    /// Returns whether the character at the start of the given bytes is a padding code
+    /// point.
+    fn is_padding(c: &[u8]) -> bool;
+
+    /// Decodes the full chunk of [`Repr::CHUNK`] bytes at the start of the buffered
+    /// bytes `data`. The index of an invalid character is the index in the chunk.
+    ///
+    /// `eof` tells whether `data` extends to the end of the stream. Errors are built from
+    /// the whole of `data`, since an invalid character may straddle the end of the chunk.
+    fn decode_chunk(data: &[u8], eof: bool, buf: &mut [u8; 7]) -> Result<(), Base16384DecodeError>;
+
+    /// Decodes the last chunk of a stream, which is either empty, a full chunk, or a
+    /// partial chunk followed by the padding code point.
+    fn decode_last_chunk<'a>(
+        last: &[u8],
+        buf: &'a mut [u8; 7],
+    ) -> Result<&'a [u8], Base16384DecodeError>;
+
+    /// Encodes a full chunk into [`Repr::CHUNK`] bytes.
+    fn encode_chunk(chunk: &[u8; 7], out: &mut [u8]);
+
+    /// Encodes a non-empty partial chunk followed by the padding code point, and returns
+    /// the number of bytes written.
+    fn encode_tail(remainder: &[u8], out: &mut [u8; 15]) -> usize;
+}
+
+impl Repr for Base16384 {
+    const CHAR: usize = 2;
+    const INDEX: usize = 2;
+
+    #[inline]
+    fn is_padding(c: &[u8]) -> bool {
+        Base16384::padding(u16::from_be_bytes([c[0], c[1]])).is_some()
+    }
+
+    #[inline]
+    fn decode_chunk(
+        data: &[u8],
+        _eof: bool,
+        buf: &mut [u8; 7],
+    ) -> Result<(), Base16384DecodeError> {
+        let mut units = Scratch::new([0u16; 4]);
+        for (u, c) in units.iter_mut().zip(data.chunks_exact(2)) {
+            *u = u16::from_be_bytes([c[0], c[1]]);
+        }
+        Base16384::decode_chunk(&units, buf)?;
+        Ok(())
+    }
+
+    fn decode_last_chunk<'a>(
+        last: &[u8],
+        buf: &'a mut [u8; 7],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        if last.len() % 2 != 0 {
+            return Err(Base16384DecodeError::InvalidLength);
+        }
+        let n = last.len() / 2;
+        let mut units = Scratch::new([0u16; 5]);
+        for (u, c) in units[..n].iter_mut().zip(last.chunks_exact(2)) {
+            *u = u16::from_be_bytes([c[0], c[1]]);
+        }
+        Base16384::decode_last_chunk(&units[..n], buf)
+    }
+
+    #[inline]
+    fn encode_chunk(chunk: &[u8; 7], out: &mut [u8]) {
+        let mut tmp = Scratch::new([0u16; 4]);
+        let encoded = Base16384::encode_chunk(chunk, &mut tmp);
+        for (c, out) in encoded.iter().zip(out.chunks_exact_mut(2)) {
+            out.copy_from_slice(&c.to_be_bytes());
+        }
+    }
+
+    fn encode_tail(remainder: &[u8], out: &mut [u8; 15]) -> usize {
+        let mut tmp = Scratch::new([0u16; 4]);
+        let encoded = Base16384::encode_remainder(remainder, &mut tmp);
+        let padding = Base16384::PADDING_OFFSET | remainder.len() as u16;
+        let mut i = 0;
+        for c in encoded.iter().chain([padding].iter()) {
+            out[i..i + 2].copy_from_slice(&c.to_be_bytes());
+            i += 2;
+        }
+        i
+    }
+}
+
+impl Repr for Base16384Utf8 {
+    const CHAR: usize = 3;
+    const INDEX: usize = 1;
+
+    #[inline]
+    fn is_padding(c: &[u8]) -> bool {
+        Base16384Utf8::padding([c[0], c[1], c[2]]).is_some()
+    }
+
+    #[inline]
+    fn decode_chunk(data: &[u8], eof: bool, buf: &mut [u8; 7]) -> Result<(), Base16384DecodeError> {
+        let chunk = data[..Self::CHUNK].try_into().unwrap();
+        match Base16384Utf8::decode_chunk(chunk, buf) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                // Same as `first_error` on the rest of the stream, if it is all buffered.
+                let padding = match eof {
+                    true => Base16384Utf8::trailing_padding(data),
+                    false => None,
+                };
+                let chars = &data[..data.len() - padding.map_or(0, |_| 3)];
+                let index = err.index().unwrap_or(0);
+                Err(Base16384Utf8::invalid_character(
+                    chars,
+                    index,
+                    eof && padding.is_none(),
+                ))
+            }
+        }
+    }
+
+    fn decode_last_chunk<'a>(
+        last: &[u8],
+        buf: &'a mut [u8; 7],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        Base16384Utf8::decode_last_chunk(last, buf)
+    }
+
+    #[inline]
+    fn encode_chunk(chunk: &[u8; 7], out: &mut [u8]) {
+        Base16384Utf8::encode_chunk(chunk, out.try_into().unwrap());
+    }
+
+    fn encode_tail(remainder: &[u8], out: &mut [u8; 15]) -> usize {
+        let mut tmp = Scratch::new([0u8; 12]);
+        let encoded = Base16384Utf8::encode_remainder(remainder, &mut tmp);
+        let i = encoded.len();
+        out[..i].copy_from_slice(encoded);
+        out[i] = Base16384Utf8::PADDING_OFFSET_HI;
+        out[i + 1] = Base16384Utf8::PADDING_OFFSET_MD;
+        out[i + 2] = Base16384Utf8::PADDING_OFFSET_LO | remainder.len() as u8;
+        i + 3
+    }
+}
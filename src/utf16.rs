@@ -0,0 +1,210 @@
+//! Byte-level UTF-16 encoding and decoding for Base16384.
+//!
+//! The original [base16384](https://github.com/fumiama/base16384) tool writes files as
+//! UTF-16BE with a `FE FF` byte order mark, which [`Base16384::encode_utf16be`] and
+//! [`Base16384::decode_utf16`] are compatible with.
+
+use core::mem::MaybeUninit;
+
+use crate::error::Base16384DecodeError;
+use crate::state::DecoderState;
+use crate::utils;
+use crate::utils::Scratch;
+use crate::Base16384;
+
+impl Base16384 {
+    /// Encodes the given data as Base16384 in UTF-16BE bytes, optionally preceded by the
+    /// byte order mark.
+    ///
+    /// With the byte order mark, the output is the same as the base16384 C tool.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_utf16be(b"12345678", true);
+    /// assert_eq!(&encoded[..4], &[0xFE, 0xFF, 0x5A, 0x4C]);
+    /// assert_eq!(encoded.len(), 14);
+    /// ```
+    pub fn encode_utf16be(data: &[u8], bom: bool) -> alloc::vec::Vec<u8> {
+        Self::encode_utf16_bytes(data, bom.then(|| Self::BOM_BE), u16::to_be_bytes)
+    }
+
+    /// Encodes the given data as Base16384 in UTF-16LE bytes, optionally preceded by the
+    /// byte order mark.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_utf16le(b"12345678", true);
+    /// assert_eq!(&encoded[..4], &[0xFF, 0xFE, 0x4C, 0x5A]);
+    /// assert_eq!(encoded.len(), 14);
+    /// ```
+    pub fn encode_utf16le(data: &[u8], bom: bool) -> alloc::vec::Vec<u8> {
+        Self::encode_utf16_bytes(data, bom.then(|| Self::BOM_LE), u16::to_le_bytes)
+    }
+
+    fn encode_utf16_bytes(
+        data: &[u8],
+        bom: Option<[u8; 2]>,
+        to_bytes: fn(u16) -> [u8; 2],
+    ) -> alloc::vec::Vec<u8> {
+        let bom = bom.as_ref().map_or(&[][..], |bom| &bom[..]);
+        let mut result = alloc::vec![0u8; bom.len() + Self::encode_len(data.len()) * 2];
+        result[..bom.len()].copy_from_slice(bom);
+
+        let mut write = |i: usize, units: &[u16]| {
+            for (j, c) in units.iter().enumerate() {
+                let i = bom.len() + (i + j) * 2;
+                result[i..i + 2].copy_from_slice(&to_bytes(*c));
+            }
+        };
+
+        // SAFETY: `encode_chunk` guarantees that N is non-zero.
+        let (chunks, remainder) = unsafe { utils::slice_as_chunks(data) };
+        let mut tmp = Scratch::new([0u16; 4]);
+        for (i, chunk) in chunks.iter().enumerate() {
+            write(i * 4, Self::encode_chunk(chunk, &mut tmp));
+        }
+        if !remainder.is_empty() {
+            let i = chunks.len() * 4;
+            let encoded = Self::encode_remainder(remainder, &mut tmp);
+            let n = encoded.len();
+            write(i, encoded);
+            write(i + n, &[Self::PADDING_OFFSET | remainder.len() as u16]);
+        }
+        result
+    }
+
+    /// Decodes the given Base16384 data in UTF-16 bytes into a new allocated vector.
+    ///
+    /// The byte order is taken from the byte order mark, and defaults to big endian
+    /// without one, like the base16384 C tool. The index of an invalid character is
+    /// the byte index in the input, including the byte order mark.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_utf16le(b"12345678", true);
+    /// let decoded = Base16384::decode_utf16(&encoded).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    ///
+    /// let encoded = Base16384::encode_utf16be(b"12345678", false);
+    /// let decoded = Base16384::decode_utf16(&encoded).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    pub fn decode_utf16(data: &[u8]) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        if data.starts_with(&Self::BOM_LE) {
+            Self::decode_utf16le(data)
+        } else {
+            Self::decode_utf16be(data)
+        }
+    }
+
+    /// Decodes the given Base16384 data in UTF-16BE bytes into a new allocated vector.
+    ///
+    /// A leading UTF-16BE byte order mark is skipped. The index of an invalid character
+    /// is the byte index in the input, including the byte order mark.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_utf16be(b"12345678", true);
+    /// let decoded = Base16384::decode_utf16be(&encoded).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    pub fn decode_utf16be(data: &[u8]) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        Self::decode_utf16_bytes(data, Self::BOM_BE, u16::from_be_bytes)
+    }
+
+    /// Decodes the given Base16384 data in UTF-16LE bytes into a new allocated vector.
+    ///
+    /// A leading UTF-16LE byte order mark is skipped. The index of an invalid character
+    /// is the byte index in the input, including the byte order mark.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_utf16le(b"12345678", false);
+    /// let decoded = Base16384::decode_utf16le(&encoded).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    /// ```
+    pub fn decode_utf16le(data: &[u8]) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        Self::decode_utf16_bytes(data, Self::BOM_LE, u16::from_le_bytes)
+    }
+
+    fn decode_utf16_bytes(
+        data: &[u8],
+        bom: [u8; 2],
+        from_bytes: fn([u8; 2]) -> u16,
+    ) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
+        let offset = if data.starts_with(&bom) { 2 } else { 0 };
+        let data = &data[offset..];
+        if data.len() % 2 != 0 {
+            return Err(Base16384DecodeError::InvalidLength);
+        }
+        let locate = |err: Base16384DecodeError| err.map_index(|index| offset + index * 2);
+
+        // SAFETY: `from_bytes` guarantees that N is non-zero,
+        // and length of data is checked to be a multiple of N.
+        let units = unsafe { utils::slice_as_chunks_exact(data) };
+        let padding = units
+            .last()
+            .and_then(|&last| Self::padding(from_bytes(last)));
+        let capacity = match Self::try_decode_len(units.len(), padding) {
+            Ok(capacity) => capacity,
+            // The first invalid character takes precedence, as in `Base16384::decode`.
+            Err(err) => {
+                let chars = &units[..units.len() - padding.is_some() as usize];
+                let first_error = chars.iter().enumerate().find_map(|(index, &bytes)| {
+                    let c = from_bytes(bytes);
+                    (!Self::is_valid_char(c)).then(|| Self::invalid_character(c, index))
+                });
+                let err = first_error.map_or(err, |err| err.at_end(units.len(), 1));
+                return Err(locate(err));
+            }
+        };
+
+        let mut result = alloc::vec::Vec::with_capacity(capacity);
+        match Self::decode_units_to_uninit(units, from_bytes, result.spare_capacity_mut()) {
+            // SAFETY: `decode_units_to_uninit` initialized the first `written` bytes.
+            Ok(written) => unsafe { result.set_len(written) },
+            Err(err) => {
+                // The chunks decoded before the error are left in the spare capacity.
+                utils::wipe(&mut result.spare_capacity_mut()[..capacity]);
+                return Err(locate(err));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Decodes the given UTF-16 code units, as bytes, into the given buffer, which must
+    /// hold their decoded length. The index of an invalid character is the index of its
+    /// code unit.
+    fn decode_units_to_uninit(
+        units: &[[u8; 2]],
+        from_bytes: fn([u8; 2]) -> u16,
+        out: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, Base16384DecodeError> {
+        let mut state = DecoderState::new();
+        let mut produced = 0;
+        let mut buf = [0u16; 256];
+        // Each batch of code units completes at most a chunk per 4 code units.
+        let mut tmp = Scratch::new([0u8; 7 * 64]);
+        for batch in units.chunks(buf.len()) {
+            for (c, bytes) in buf.iter_mut().zip(batch) {
+                *c = from_bytes(*bytes);
+            }
+            let (_, n) = state.update(&buf[..batch.len()], &mut tmp[..])?;
+            utils::write_slice(&mut out[produced..], &tmp[..n]);
+            produced += n;
+        }
+        let n = state.finalize(&mut tmp[..])?;
+        utils::write_slice(&mut out[produced..], &tmp[..n]);
+        Ok(produced + n)
+    }
+}
@@ -1,13 +1,38 @@
 //! UTF-8 encoding and decoding for Base16384.
 
-use crate::error::Base16384DecodeError;
+use core::mem::MaybeUninit;
+
+use crate::codec;
+use crate::error::{Base16384DecodeError, Base16384EncodeError};
 use crate::utils;
 use crate::utils::slice_as_chunks_exact;
+use crate::utils::Scratch;
 use crate::Base16384;
 
 /// UTF-8 encoding and decoding for Base16384.
 pub struct Base16384Utf8;
 
+/// Displays the given data as Base16384, without allocating.
+///
+/// The encoded text is written to the formatter chunk by chunk, so width, fill and
+/// alignment flags are ignored.
+///
+/// # Examples
+/// ```
+/// use base16384::Base16384Display;
+///
+/// let text = format!("id: {}", Base16384Display(b"12345678"));
+/// assert_eq!(text, "id: 婌焳廔萷尀㴁");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Base16384Display<'a>(pub &'a [u8]);
+
+impl core::fmt::Display for Base16384Display<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Base16384Utf8::encode_to_fmt(self.0, f)
+    }
+}
+
 impl Base16384Utf8 {
     /// Returns the minimum number of bytes needed to encode a `data_len`-byte.
     ///
@@ -35,9 +60,9 @@ impl Base16384Utf8 {
     #[allow(clippy::assertions_on_constants)]
     const START_LO_MUST_BE_ZERO: () = assert!(Self::START_LO == 0, "START_LO must be 0");
 
-    const PADDING_OFFSET_HI: u8 = 0xE0 | (Base16384::PADDING_OFFSET >> 12) as u8;
-    const PADDING_OFFSET_MD: u8 = 0x80 | ((Base16384::PADDING_OFFSET >> 6) & 0x3F) as u8;
-    const PADDING_OFFSET_LO: u8 = 0x80 | (Base16384::PADDING_OFFSET & 0x3F) as u8;
+    pub(crate) const PADDING_OFFSET_HI: u8 = 0xE0 | (Base16384::PADDING_OFFSET >> 12) as u8;
+    pub(crate) const PADDING_OFFSET_MD: u8 = 0x80 | ((Base16384::PADDING_OFFSET >> 6) & 0x3F) as u8;
+    pub(crate) const PADDING_OFFSET_LO: u8 = 0x80 | (Base16384::PADDING_OFFSET & 0x3F) as u8;
 
     /// Encodes the given data as Base16384 in a new allocated [`String`].
     ///
@@ -54,31 +79,44 @@ impl Base16384Utf8 {
     /// [`String`]: alloc::string::String
     #[cfg(any(feature = "std", test, feature = "alloc"))]
     pub fn encode(data: &[u8]) -> alloc::string::String {
-        let capacity = Self::encode_len(data.len());
-        let mut result = alloc::vec::Vec::with_capacity(capacity);
+        let mut result = alloc::string::String::new();
+        Self::encode_into_string(data, &mut result);
+        result
+    }
 
-        // SAFETY: `encode_chunk` guarantees that N is non-zero.
-        let (chunks, remainder) = unsafe { utils::slice_as_chunks(data) };
-        for chunk in chunks {
-            let mut buf = [0u8; 12];
-            result.extend_from_slice(Self::encode_chunk(chunk, &mut buf));
-        }
-        if !remainder.is_empty() {
-            let mut buf = [0u8; 12];
-            result.extend_from_slice(Self::encode_remainder(remainder, &mut buf));
-            result.extend([
-                Self::PADDING_OFFSET_HI,
-                Self::PADDING_OFFSET_MD,
-                Self::PADDING_OFFSET_LO | (remainder.len() as u8),
-            ]);
-        }
-        unsafe { alloc::string::String::from_utf8_unchecked(result) }
+    /// Encodes the given data as Base16384, appending it to the given [`String`].
+    ///
+    /// Exactly [`Base16384Utf8::encode_len`] bytes are reserved, so the string can be
+    /// reused across calls without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let mut encoded = String::from("data: ");
+    /// Base16384Utf8::encode_into_string(b"12345678", &mut encoded);
+    /// assert_eq!(encoded, "data: 婌焳廔萷尀㴁");
+    /// ```
+    ///
+    /// [`String`]: alloc::string::String
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn encode_into_string(data: &[u8], out: &mut alloc::string::String) {
+        let len = out.len();
+        let additional = Self::encode_len(data.len());
+        // SAFETY: only the bytes written by `encode_to_uninit`, which are valid UTF-8,
+        // are added to the string.
+        let out = unsafe { out.as_mut_vec() };
+        out.reserve_exact(additional);
+        let written = Self::encode_to_uninit(data, out.spare_capacity_mut());
+        // SAFETY: `encode_to_uninit` initialized the first `written` bytes.
+        unsafe { out.set_len(len + written) };
     }
 
     /// Encodes the given data as Base16384 into the given buffer.
     ///
     /// # Panics
     /// Panics if the buffer is too small. Use [`Base16384Utf8::encode_len`] to get the required capacity.
+    /// See [`Base16384Utf8::try_encode_to_slice`] for a non-panicking variant.
     ///
     /// # Examples
     /// ```
@@ -92,32 +130,103 @@ impl Base16384Utf8 {
     /// ```
     pub fn encode_to_slice<'a>(data: &[u8], buf: &'a mut str) -> &'a str {
         let buf = unsafe { buf.as_bytes_mut() };
+        let len = Self::encode_to_bytes(data, buf);
+        unsafe { core::str::from_utf8_unchecked(&buf[..len]) }
+    }
+
+    /// Encodes the given data into the given buffer, and returns the encoded length.
+    pub(crate) fn encode_to_bytes(data: &[u8], buf: &mut [u8]) -> usize {
+        // SAFETY: `encode_to_uninit` only writes initialized bytes.
+        Self::encode_to_uninit(data, unsafe { utils::as_uninit_mut(buf) })
+    }
+
+    /// Encodes the given data into the given buffer, and returns the encoded length. The
+    /// buffer may be uninitialized, e.g. the spare capacity of a vector.
+    pub(crate) fn encode_to_uninit(data: &[u8], buf: &mut [MaybeUninit<u8>]) -> usize {
         let capacity = Self::encode_len(data.len());
         assert!(buf.len() >= capacity, "buffer is too small");
 
         // SAFETY: `encode_chunk` guarantees that N is non-zero.
         let (chunks, remainder) = unsafe { utils::slice_as_chunks(data) };
+        let mut tmp = Scratch::new([0u8; 12]);
         let mut i = 0;
         for chunk in chunks {
-            let mut tmp = [0u8; 12];
-            buf[i..i + 12].copy_from_slice(Self::encode_chunk(chunk, &mut tmp));
+            utils::write_slice(&mut buf[i..], Self::encode_chunk(chunk, &mut tmp));
             i += 12;
         }
         if !remainder.is_empty() {
-            let mut tmp = [0u8; 12];
             let encoded = Self::encode_remainder(remainder, &mut tmp);
-            buf[i..i + encoded.len()].copy_from_slice(encoded);
+            utils::write_slice(&mut buf[i..], encoded);
             i += encoded.len();
-            buf[i] = Self::PADDING_OFFSET_HI;
-            buf[i + 1] = Self::PADDING_OFFSET_MD;
-            buf[i + 2] = Self::PADDING_OFFSET_LO | (remainder.len() as u8);
+            let padding = Self::PADDING_OFFSET_LO | (remainder.len() as u8);
+            utils::write_slice(
+                &mut buf[i..],
+                &[Self::PADDING_OFFSET_HI, Self::PADDING_OFFSET_MD, padding],
+            );
             i += 3;
         }
-        unsafe { core::str::from_utf8_unchecked(&buf[..i]) }
+        i
+    }
+
+    /// Encodes the given data as Base16384 into the given buffer, or returns an error if
+    /// the buffer is too small.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    /// use base16384::error::Base16384EncodeError;
+    ///
+    /// let data = b"12345678";
+    /// let mut buf = "A".repeat(18);
+    /// let encoded = Base16384Utf8::try_encode_to_slice(data, &mut buf).unwrap();
+    /// assert_eq!(encoded, "婌焳廔萷尀㴁");
+    ///
+    /// let mut buf = "A".repeat(17);
+    /// let err = Base16384Utf8::try_encode_to_slice(data, &mut buf).unwrap_err();
+    /// assert_eq!(err, Base16384EncodeError::BufferTooSmall { needed: 18, available: 17 });
+    /// ```
+    pub fn try_encode_to_slice<'a>(
+        data: &[u8],
+        buf: &'a mut str,
+    ) -> Result<&'a str, Base16384EncodeError> {
+        let needed = Self::encode_len(data.len());
+        if buf.len() < needed {
+            return Err(Base16384EncodeError::BufferTooSmall {
+                needed,
+                available: buf.len(),
+            });
+        }
+        Ok(Self::encode_to_slice(data, buf))
+    }
+
+    /// Encodes the given data as Base16384 into the given [`fmt::Write`] sink, without
+    /// allocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let mut text = String::new();
+    /// Base16384Utf8::encode_to_fmt(b"12345678", &mut text).unwrap();
+    /// assert_eq!(text, "婌焳廔萷尀㴁");
+    /// ```
+    ///
+    /// [`fmt::Write`]: core::fmt::Write
+    pub fn encode_to_fmt(data: &[u8], w: &mut impl core::fmt::Write) -> core::fmt::Result {
+        const BATCH: usize = 64;
+
+        // The last batch may be partial, and take 3 more bytes for the padding.
+        let mut buf = [0u8; Self::encode_len(BATCH * 7) + 3];
+        // SAFETY: the buffer is all ASCII.
+        let buf = unsafe { core::str::from_utf8_unchecked_mut(&mut buf) };
+        for batch in data.chunks(BATCH * 7) {
+            w.write_str(Self::encode_to_slice(batch, buf))?;
+        }
+        Ok(())
     }
 
     #[inline]
-    fn encode_chunk<'a>(chunk: &[u8; 7], buf: &'a mut [u8; 12]) -> &'a [u8; 12] {
+    pub(crate) fn encode_chunk<'a>(chunk: &[u8; 7], buf: &'a mut [u8; 12]) -> &'a [u8; 12] {
         let b0_hi = chunk[0] as u16 + Self::START_HI;
         let b0_lo = chunk[1] >> 2;
         buf[0] = 0xE0 | (b0_hi >> 6) as u8;
@@ -146,8 +255,8 @@ impl Base16384Utf8 {
     }
 
     #[inline]
-    fn encode_remainder<'a>(remainder: &[u8], buf: &'a mut [u8; 12]) -> &'a [u8] {
-        let mut chunk = [0u8; 7];
+    pub(crate) fn encode_remainder<'a>(remainder: &[u8], buf: &'a mut [u8; 12]) -> &'a [u8] {
+        let mut chunk = Scratch::new([0u8; 7]);
         chunk[..remainder.len()].copy_from_slice(remainder);
         Self::encode_chunk(&chunk, buf);
         &buf[..(remainder.len() / 2 + 1) * 3]
@@ -156,9 +265,9 @@ impl Base16384Utf8 {
     /// Returns the minimum number of bytes needed to decode the given number of bytes of utf8 data.
     /// The given offset is the padding code point of the last chunk (if exists).
     ///
-    /// # Panics
-    /// Panics if the given offset is out of the Base16384 padding code points range
-    /// (see [`Base16384::PADDING_OFFSET`]).
+    /// The result is meaningless if the given offset is out of the Base16384 padding code
+    /// points range (see [`Base16384::PADDING_OFFSET`]), or if `data_len` is not a
+    /// multiple of 3. See [`Base16384Utf8::try_decode_len`] to check them.
     ///
     /// # Examples
     /// ```
@@ -168,13 +277,54 @@ impl Base16384Utf8 {
     /// let padding = Base16384Utf8::padding(data.as_bytes()[data.len() - 3..].try_into().unwrap());
     /// let decoded_len = Base16384Utf8::decode_len(data.len(), padding);
     /// assert_eq!(decoded_len, 8);
+    ///
+    /// // Untrusted lengths are validated by `try_decode_len` instead.
+    /// assert_eq!(Base16384Utf8::try_decode_len(data.len(), padding), Ok(8));
+    /// assert!(Base16384Utf8::try_decode_len(data.len() - 3, padding).is_err());
     /// ```
     #[inline]
     pub fn decode_len(data_len: usize, padding: Option<u16>) -> usize {
-        assert!(data_len % 3 == 0, "data_len must be a multiple of 3");
         Base16384::decode_len(data_len / 3, padding)
     }
 
+    /// Returns the number of bytes needed to decode the given number of bytes of utf8
+    /// data, or an error if no valid Base16384 data has the given length and padding
+    /// code point.
+    ///
+    /// This is the checked counterpart of [`Base16384Utf8::decode_len`].
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// assert_eq!(Base16384Utf8::try_decode_len(18, Some(0x3d01)), Ok(8));
+    /// assert!(Base16384Utf8::try_decode_len(17, Some(0x3d01)).is_err());
+    /// ```
+    #[inline]
+    pub fn try_decode_len(
+        data_len: usize,
+        padding: Option<u16>,
+    ) -> Result<usize, Base16384DecodeError> {
+        if data_len % 3 != 0 {
+            return Err(Base16384DecodeError::InvalidLength);
+        }
+        Base16384::try_decode_len(data_len / 3, padding).map_err(|e| e.map_index(|i| i * 3))
+    }
+
+    /// Returns the number of bytes needed to decode the given utf8 data, or the first
+    /// error in it if it has an invalid length.
+    pub(crate) fn checked_decode_len(data: &[u8]) -> Result<usize, Base16384DecodeError> {
+        let padding = Self::trailing_padding(data);
+        Self::try_decode_len(data.len(), padding)
+            .map_err(|err| Self::first_error(data, padding).unwrap_or(err))
+    }
+
+    /// Gets the padding code point at the end of the given utf8 data (if exists).
+    pub(crate) fn trailing_padding(data: &[u8]) -> Option<u16> {
+        let i = data.len().checked_sub(3)?;
+        Self::padding([data[i], data[i + 1], data[i + 2]])
+    }
+
     /// Gets the padding code point of the last chunk (if exists).
     ///
     /// # Examples
@@ -212,60 +362,70 @@ impl Base16384Utf8 {
     /// ```
     #[cfg(any(feature = "std", test, feature = "alloc"))]
     pub fn decode(data: &str) -> Result<alloc::vec::Vec<u8>, Base16384DecodeError> {
-        if data.is_empty() {
-            return Ok(alloc::vec::Vec::new());
-        }
-        if data.len() % 3 != 0 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
-        if data.len() < 3 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
+        let mut result = alloc::vec::Vec::new();
+        Self::decode_into_vec(data, &mut result)?;
+        Ok(result)
+    }
 
-        let data = data.as_bytes();
-        let padding = &data[data.len() - 3..];
-        let padding = Self::padding(padding.try_into().unwrap());
-        let capacity = Self::decode_len(data.len(), padding);
-        let mut result = alloc::vec::Vec::with_capacity(capacity);
-
-        let padding_size = padding.map(|padding| padding - Base16384::PADDING_OFFSET);
-        let last_chunk_size = padding_size.map(|padding_size| match padding_size {
-            0 => 1,
-            1 => 2,
-            2 | 3 => 3,
-            4 | 5 => 4,
-            6 => 5,
-            _ => unreachable!(),
-        });
-
-        let (data, remainder) = if let Some(last_chunk_size) = last_chunk_size {
-            let (data, remainder) = data.split_at(data.len() - last_chunk_size * 3);
-            (data, &remainder[..remainder.len() - 3])
-        } else {
-            (data, &[][..])
-        };
-        if data.len() % 4 != 0 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
+    /// Decodes the given data like [`Base16384Utf8::decode`], into a vector that is wiped
+    /// when dropped. Use it for secret material, such as keys.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let decoded = Base16384Utf8::decode_zeroizing("婌焳廔萷尀㴁").unwrap();
+    /// assert_eq!(*decoded, b"12345678");
+    /// ```
+    #[cfg(all(feature = "zeroize", any(feature = "std", test, feature = "alloc")))]
+    pub fn decode_zeroizing(
+        data: &str,
+    ) -> Result<zeroize::Zeroizing<alloc::vec::Vec<u8>>, Base16384DecodeError> {
+        let mut result = zeroize::Zeroizing::new(alloc::vec::Vec::new());
+        Self::decode_into_vec(data, &mut result)?;
+        Ok(result)
+    }
 
-        // SAFETY: `decode_chunk` guarantees that N is non-zero,
-        // and length of data is checked to be a multiple of N.
-        let chunks = unsafe { utils::slice_as_chunks_exact(data) };
-        for chunk in chunks {
-            let mut buf = [0u8; 7];
-            result.extend_from_slice(Self::decode_chunk(chunk, &mut buf)?);
+    /// Decodes the given utf8 data as Base16384, appending it to the given vector.
+    ///
+    /// Exactly [`Base16384Utf8::decode_len`] bytes are reserved, so the vector can be
+    /// reused across calls without reallocating. On error, the vector is left as is.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let mut decoded = b"abc".to_vec();
+    /// Base16384Utf8::decode_into_vec("婌焳廔萷尀㴁", &mut decoded).unwrap();
+    /// assert_eq!(decoded, b"abc12345678");
+    /// ```
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    pub fn decode_into_vec(
+        data: &str,
+        out: &mut alloc::vec::Vec<u8>,
+    ) -> Result<(), Base16384DecodeError> {
+        if data.is_empty() {
+            return Ok(());
         }
-        if let Some(padding_size) = padding_size {
-            let mut buf = [0u8; 7];
-            result.extend_from_slice(Self::decode_remainder(remainder, &mut buf, padding_size)?);
+        let len = out.len();
+        let additional = Self::checked_decode_len(data.as_bytes())?;
+        out.reserve_exact(additional);
+        match Self::decode_to_uninit(data.as_bytes(), out.spare_capacity_mut()) {
+            // SAFETY: `decode_to_uninit` initialized the first `written` bytes.
+            Ok(written) => unsafe { out.set_len(len + written) },
+            Err(err) => {
+                // The chunks decoded before the error are left in the spare capacity.
+                utils::wipe(&mut out.spare_capacity_mut()[..additional]);
+                return Err(err);
+            }
         }
-        Ok(result)
+        Ok(())
     }
 
     /// Decodes the given utf8 data as Base16384 into the given buffer.
     ///
-    /// # Panics
-    /// Panics if the buffer is too small. Use [`Base16384Utf8::decode_len`] to get the required capacity.
+    /// Returns [`Base16384DecodeError::BufferTooSmall`] if the buffer is too small. Use
+    /// [`Base16384Utf8::decode_len`] to get the required capacity.
     ///
     /// # Examples
     /// ```
@@ -280,110 +440,225 @@ impl Base16384Utf8 {
         data: &str,
         buf: &'a mut [u8],
     ) -> Result<&'a [u8], Base16384DecodeError> {
-        if data.is_empty() {
-            return Ok(&buf[..0]);
-        }
-        if data.len() % 3 != 0 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
-        if data.len() < 3 {
-            return Err(Base16384DecodeError::InvalidLength);
-        }
+        Self::decode_bytes(data.as_bytes(), buf)
+    }
 
-        let data = data.as_bytes();
-        let padding = &data[data.len() - 3..];
-        let padding = Self::padding(padding.try_into().unwrap());
-        let capacity = Self::decode_len(data.len(), padding);
-        assert!(buf.len() >= capacity, "buffer is too small");
+    pub(crate) fn decode_bytes<'a>(
+        data: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        // SAFETY: `decode_to_uninit` only writes initialized bytes.
+        let len = Self::decode_to_uninit(data, unsafe { utils::as_uninit_mut(buf) })?;
+        Ok(&buf[..len])
+    }
 
-        let padding_size = padding.map(|padding| padding - Base16384::PADDING_OFFSET);
-        let last_chunk_size = padding_size.map(|padding_size| match padding_size {
-            0 => 1,
-            1 => 2,
-            2 | 3 => 3,
-            4 | 5 => 4,
-            6 => 5,
-            _ => unreachable!(),
-        });
-
-        let (data, remainder) = if let Some(last_chunk_size) = last_chunk_size {
-            let (data, remainder) = data.split_at(data.len() - last_chunk_size * 3);
-            (data, &remainder[..remainder.len() - 3])
-        } else {
-            (data, &[][..])
-        };
-        if data.len() % 4 != 0 {
-            return Err(Base16384DecodeError::InvalidLength);
+    /// Decodes the given data into the given buffer, and returns the decoded length. The
+    /// buffer may be uninitialized, e.g. the spare capacity of a vector.
+    pub(crate) fn decode_to_uninit(
+        data: &[u8],
+        buf: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, Base16384DecodeError> {
+        if data.is_empty() {
+            return Ok(0);
         }
 
+        let padding = Self::trailing_padding(data);
+        let capacity = Self::checked_decode_len(data)?;
+        codec::check_buffer(capacity, buf.len())?;
+        // Errors are located in the whole data, since chunks hold no position.
+        let first_error = |err| Self::first_error(data, padding).unwrap_or(err);
+        let (chunks, remainder, padding_size) = codec::split_last_chunk(data, padding, 3)?;
+
         // SAFETY: `decode_chunk` guarantees that N is non-zero,
         // and length of data is checked to be a multiple of N.
-        let chunks = unsafe { utils::slice_as_chunks_exact(data) };
+        let chunks = unsafe { utils::slice_as_chunks_exact(chunks) };
+        let mut tmp = Scratch::new([0u8; 7]);
         let mut i = 0;
         for chunk in chunks {
-            let mut tmp = [0u8; 7];
-            let decoded = Self::decode_chunk(chunk, &mut tmp)?;
-            buf[i..i + 7].copy_from_slice(decoded);
+            let decoded = Self::decode_chunk(chunk, &mut tmp).map_err(first_error)?;
+            utils::write_slice(&mut buf[i..], decoded);
             i += 7;
         }
-        if let Some(padding_size) = padding_size {
-            let mut tmp = [0u8; 7];
-            let decoded = Self::decode_remainder(remainder, &mut tmp, padding_size)?;
-            buf[i..i + decoded.len()].copy_from_slice(decoded);
+        if padding.is_some() {
+            let decoded =
+                Self::decode_remainder(remainder, &mut tmp, padding_size).map_err(first_error)?;
+            utils::write_slice(&mut buf[i..], decoded);
             i += decoded.len();
         }
-        Ok(&buf[..i])
+        Ok(i)
+    }
+
+    /// Decodes the given utf8 data as Base16384 into the given buffer, or returns an
+    /// error if the buffer is too small.
+    ///
+    /// This is the same as [`Base16384Utf8::decode_to_slice`], which checks the size of
+    /// the buffer as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use base16384::Base16384Utf8;
+    /// use base16384::error::Base16384DecodeError;
+    ///
+    /// let data = "婌焳廔萷尀㴁";
+    /// let mut buf = [0u8; 8];
+    /// let decoded = Base16384Utf8::try_decode_to_slice(data, &mut buf).unwrap();
+    /// assert_eq!(decoded, b"12345678");
+    ///
+    /// let mut buf = [0u8; 7];
+    /// let err = Base16384Utf8::try_decode_to_slice(data, &mut buf).unwrap_err();
+    /// assert_eq!(err, Base16384DecodeError::BufferTooSmall { needed: 8, available: 7 });
+    /// ```
+    #[deprecated(note = "use `decode_to_slice`, which returns the same errors")]
+    pub fn try_decode_to_slice<'a>(
+        data: &str,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        Self::decode_to_slice(data, buf)
+    }
+
+    /// Checks the given utf8 data as Base16384 without decoding it, and returns the
+    /// number of bytes it decodes to.
+    ///
+    /// This accepts exactly the data accepted by [`Base16384Utf8::decode`], without
+    /// needing an output buffer. The index of an invalid character is its byte index in
+    /// the data.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::Base16384Utf8;
+    /// use base16384::error::Base16384DecodeError;
+    ///
+    /// assert_eq!(Base16384Utf8::validate("婌焳廔萷尀㴁"), Ok(8));
+    ///
+    /// let err = Base16384Utf8::validate("婌焳廔萷x尀㴁").unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     Base16384DecodeError::InvalidCharacter {
+    ///         index: 12,
+    ///         value: 'x' as u32,
+    ///         padding_position: false,
+    ///     }
+    /// );
+    /// ```
+    pub fn validate(data: &str) -> Result<usize, Base16384DecodeError> {
+        let data = data.as_bytes();
+        let padding = Self::trailing_padding(data);
+        match Self::first_error(data, padding) {
+            Some(err) => Err(err),
+            None => Self::try_decode_len(data.len(), padding),
+        }
     }
 
     #[inline]
-    fn valid_char(c: [u8; 3]) -> Option<u16> {
+    pub(crate) fn valid_char(c: [u8; 3]) -> Option<u16> {
+        if c[0] & 0xF0 != 0xE0 || c[1] & 0xC0 != 0x80 || c[2] & 0xC0 != 0x80 {
+            return None;
+        }
         let b0 = (c[0] & 0x0F) as u16;
         let b1 = (c[1] & 0x3F) as u16;
         let b2 = (c[2] & 0x3F) as u16;
         let c = b0 << 12 | b1 << 6 | b2;
-        if (Base16384::START..Base16384::START + 0x3FFF).contains(&c) {
+        if (Base16384::START..=Base16384::START + 0x3FFF).contains(&c) {
             Some(c)
         } else {
             None
         }
     }
 
+    /// Returns the error for the character at the given byte index in the data, which is
+    /// not in the alphabet.
+    ///
+    /// `at_end` tells whether the data extends to the end of the input, in which case a
+    /// character cut short by the end is truncated, rather than unknown.
+    pub(crate) fn invalid_character(
+        data: &[u8],
+        index: usize,
+        at_end: bool,
+    ) -> Base16384DecodeError {
+        let rest = &data[index..];
+        let width = match rest[0] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            _ => 4,
+        };
+        let c = rest
+            .get(..width)
+            .and_then(|c| core::str::from_utf8(c).ok())
+            .and_then(|c| c.chars().next());
+        match c {
+            Some(c) if width == 3 && Base16384::padding(c as u16).is_some() => {
+                Base16384DecodeError::PaddingNotAtEnd { index }
+            }
+            Some(c) => Base16384DecodeError::InvalidCharacter {
+                index,
+                value: c as u32,
+                padding_position: at_end && index + width == data.len(),
+            },
+            None if at_end
+                && rest.len() < width
+                && core::str::from_utf8(rest)
+                    .map_or_else(|e| e.error_len().is_none(), |_| false) =>
+            {
+                Base16384DecodeError::TruncatedCharacter { index }
+            }
+            None => Base16384DecodeError::InvalidCharacter {
+                index,
+                value: char::REPLACEMENT_CHARACTER as u32,
+                padding_position: false,
+            },
+        }
+    }
+
+    /// Returns the error for the first character outside of the alphabet in the given
+    /// utf8 data, other than the trailing padding code point, if any.
+    pub(crate) fn first_error(data: &[u8], padding: Option<u16>) -> Option<Base16384DecodeError> {
+        let chars = &data[..data.len() - padding.map_or(0, |_| 3)];
+        let mut i = 0;
+        while i < chars.len() {
+            match chars.get(i..i + 3) {
+                Some(c) if Self::valid_char([c[0], c[1], c[2]]).is_some() => i += 3,
+                _ => return Some(Self::invalid_character(chars, i, padding.is_none())),
+            }
+        }
+        None
+    }
+
+    /// Decodes a chunk. The index of an invalid character is the index in the chunk.
     #[inline]
-    fn decode_chunk<'a>(
+    pub(crate) fn decode_chunk<'a>(
         chunk: &[u8; 12],
         buf: &'a mut [u8; 7],
     ) -> Result<&'a [u8; 7], Base16384DecodeError> {
-        let mut chars = [0u16; 4];
+        let mut offsets = Scratch::new([0u16; 4]);
+        #[cfg(not(feature = "ct"))]
         unsafe {
             for (i, c) in slice_as_chunks_exact(chunk).iter().enumerate() {
-                let c = Self::valid_char(*c)
-                    .ok_or(Base16384DecodeError::InvalidCharacter { index: i * 3 })?;
-                chars[i] = c;
+                offsets[i] = Self::valid_char(*c)
+                    .ok_or_else(|| Self::invalid_character(chunk, i * 3, false))?
+                    - Base16384::START;
             }
-        };
-        let b0 = chars[0] - Base16384::START;
-        let b1 = chars[1] - Base16384::START;
-        let b2 = chars[2] - Base16384::START;
-        let b3 = chars[3] - Base16384::START;
-
-        buf[0] = (b0 >> 6) as u8;
-        buf[1] = ((b0 & 0x3F) << 2 | (b1 >> 12)) as u8;
-        buf[2] = (b1 >> 4) as u8;
-        buf[3] = ((b1 & 0x0F) << 4 | (b2 >> 10)) as u8;
-        buf[4] = (b2 >> 2) as u8;
-        buf[5] = ((b2 & 0x03) << 6 | (b3 >> 8)) as u8;
-        buf[6] = b3 as u8;
-
-        Ok(buf)
+        }
+        // The whole chunk is checked at once, without branching on each character.
+        #[cfg(feature = "ct")]
+        if crate::ct::utf8_offsets(chunk, &mut offsets) != 0 {
+            let i = unsafe { slice_as_chunks_exact::<_, 3>(chunk) }
+                .iter()
+                .position(|&c| Self::valid_char(c).is_none())
+                .unwrap_or(0);
+            return Err(Self::invalid_character(chunk, i * 3, false));
+        }
+        Ok(Base16384::unpack(&offsets, buf))
     }
 
     #[inline]
-    fn decode_remainder<'a>(
+    pub(crate) fn decode_remainder<'a>(
         remainder: &[u8],
         buf: &'a mut [u8; 7],
-        padding_size: u16,
+        padding_size: usize,
     ) -> Result<&'a [u8], Base16384DecodeError> {
-        let mut chunk = [
+        let mut chunk = Scratch::new([
             Self::START_UTF8_HI,
             Self::START_UTF8_MD,
             Self::START_UTF8_LO,
@@ -396,9 +671,34 @@ impl Base16384Utf8 {
             Self::START_UTF8_HI,
             Self::START_UTF8_MD,
             Self::START_UTF8_LO,
-        ];
+        ]);
         chunk[..remainder.len()].copy_from_slice(remainder);
         Self::decode_chunk(&chunk, buf)?;
-        Ok(&buf[..padding_size as usize])
+        Ok(&buf[..padding_size])
+    }
+
+    /// Decodes the last chunk of a stream, which is either empty, a full chunk, or a
+    /// partial chunk followed by the padding code point.
+    #[cfg(feature = "std")]
+    pub(crate) fn decode_last_chunk<'a>(
+        last: &[u8],
+        buf: &'a mut [u8; 7],
+    ) -> Result<&'a [u8], Base16384DecodeError> {
+        let padding = Self::trailing_padding(last);
+        // Invalid characters are only looked for on errors, and take precedence.
+        let first_error = |err| Self::first_error(last, padding).unwrap_or(err);
+        match padding {
+            Some(padding) => {
+                let padding_size =
+                    Self::try_decode_len(last.len(), Some(padding)).map_err(first_error)?;
+                Self::decode_remainder(&last[..last.len() - 3], buf, padding_size)
+                    .map_err(first_error)
+            }
+            None => match last.try_into() {
+                Ok(chunk) => Ok(&Self::decode_chunk(chunk, buf).map_err(first_error)?[..]),
+                Err(_) if last.is_empty() => Ok(&buf[..0]),
+                Err(_) => Err(first_error(Base16384DecodeError::InvalidLength)),
+            },
+        }
     }
 }
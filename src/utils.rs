@@ -1,5 +1,7 @@
 //! Utility functions for the crate.
 
+use core::mem::MaybeUninit;
+
 /// Splits the slice into a slice of `N`-element arrays,
 /// starting at the beginning of the slice,
 /// and a remainder slice with length strictly less than `N`.
@@ -46,3 +48,75 @@ pub unsafe fn slice_as_chunks_exact<T, const N: usize>(arr: &[T]) -> &[[T; N]] {
     let len = arr.len() / N;
     core::slice::from_raw_parts(arr.as_ptr().cast(), len)
 }
+
+/// Views an initialized slice as a possibly uninitialized one, so that the same code can
+/// write into it and into the spare capacity of a vector.
+///
+/// # Safety
+/// Only initialized values may be written to the returned slice.
+#[inline]
+pub(crate) unsafe fn as_uninit_mut<T>(buf: &mut [T]) -> &mut [MaybeUninit<T>] {
+    &mut *(buf as *mut [T] as *mut [MaybeUninit<T>])
+}
+
+/// Copies `src` to the start of `dst`.
+///
+/// # Panics
+/// Panics if `dst` is shorter than `src`.
+#[inline]
+pub(crate) fn write_slice<T: Copy>(dst: &mut [MaybeUninit<T>], src: &[T]) {
+    for (d, &s) in dst[..src.len()].iter_mut().zip(src) {
+        *d = MaybeUninit::new(s);
+    }
+}
+
+/// A temporary buffer wiped when dropped, if the `zeroize` feature is enabled.
+#[cfg(feature = "zeroize")]
+pub(crate) use zeroize::Zeroizing as Scratch;
+
+/// A temporary buffer wiped when dropped, if the `zeroize` feature is enabled.
+#[cfg(not(feature = "zeroize"))]
+pub(crate) struct Scratch<T>(T);
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> Scratch<T> {
+    #[inline]
+    pub(crate) fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> core::ops::Deref for Scratch<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> core::ops::DerefMut for Scratch<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Wipes the given bytes, if the `zeroize` feature is enabled.
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+#[inline]
+pub(crate) fn wipe(_buf: &mut [MaybeUninit<u8>]) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(_buf);
+}
+
+/// Wipes the given vector, including its spare capacity, and clears it, if the `zeroize`
+/// feature is enabled.
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+#[inline]
+pub(crate) fn wipe_vec(_vec: &mut alloc::vec::Vec<u8>) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(_vec);
+}
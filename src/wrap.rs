@@ -0,0 +1,158 @@
+//! Line-wrapped encoding.
+//!
+//! See [`Base16384::encode_wrapped`] and [`Base16384Utf8::encode_wrapped`]. The wrapped
+//! output is decoded by [`Base16384::decode_lenient`] and
+//! [`Base16384Utf8::decode_lenient`] with the default ignore set.
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+use crate::{Base16384, Base16384Utf8};
+
+/// The separator inserted between lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`.
+    Lf,
+    /// `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the separator as a string.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Where lines are wrapped when encoding.
+///
+/// # Examples
+/// ```
+/// use base16384::wrap::{LineEnding, LineWrap};
+///
+/// let wrap = LineWrap::columns(80).with_line_ending(LineEnding::CrLf);
+/// assert_eq!(wrap.chars_per_line(), 40);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineWrap {
+    chars_per_line: usize,
+    line_ending: LineEnding,
+}
+
+impl LineWrap {
+    /// Wraps lines every `n` characters, with `\n`.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub const fn chars(n: usize) -> Self {
+        assert!(n > 0, "line width must be positive");
+        Self {
+            chars_per_line: n,
+            line_ending: LineEnding::Lf,
+        }
+    }
+
+    /// Wraps lines to fit `n` terminal columns, with `\n`.
+    ///
+    /// Every Base16384 character is full-width, taking 2 columns. At least one character
+    /// is put on each line.
+    pub const fn columns(n: usize) -> Self {
+        Self::chars(if n < 2 { 1 } else { n / 2 })
+    }
+
+    /// Sets the separator inserted between lines.
+    pub const fn with_line_ending(self, line_ending: LineEnding) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+
+    /// Returns the number of characters on each line, except for the last one.
+    pub const fn chars_per_line(&self) -> usize {
+        self.chars_per_line
+    }
+
+    /// Returns the separator inserted between lines.
+    pub const fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Returns the number of separators needed for the given number of characters.
+    #[cfg(any(feature = "std", test, feature = "alloc"))]
+    const fn separators(&self, chars: usize) -> usize {
+        if chars == 0 {
+            0
+        } else {
+            (chars - 1) / self.chars_per_line
+        }
+    }
+}
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+impl Base16384 {
+    /// Encodes the given data as Base16384 in a new allocated vector, with a line
+    /// separator after every full line.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::wrap::LineWrap;
+    /// use base16384::Base16384;
+    ///
+    /// let encoded = Base16384::encode_wrapped(b"12345678", LineWrap::columns(8));
+    /// assert_eq!(String::from_utf16(&encoded).unwrap(), "婌焳廔萷\n尀㴁");
+    /// ```
+    pub fn encode_wrapped(data: &[u8], wrap: LineWrap) -> alloc::vec::Vec<u16> {
+        let encoded = Self::encode(data);
+        let separator = wrap.line_ending.as_str();
+        let mut result = alloc::vec::Vec::with_capacity(
+            encoded.len() + wrap.separators(encoded.len()) * separator.len(),
+        );
+        for (i, line) in encoded.chunks(wrap.chars_per_line).enumerate() {
+            if i > 0 {
+                result.extend(separator.bytes().map(u16::from));
+            }
+            result.extend_from_slice(line);
+        }
+        result
+    }
+}
+
+#[cfg(any(feature = "std", test, feature = "alloc"))]
+impl Base16384Utf8 {
+    /// Encodes the given data as Base16384 in a new allocated [`String`], with a line
+    /// separator after every full line.
+    ///
+    /// # Examples
+    /// ```
+    /// use base16384::wrap::{LineEnding, LineWrap};
+    /// use base16384::Base16384Utf8;
+    ///
+    /// let wrap = LineWrap::chars(4).with_line_ending(LineEnding::CrLf);
+    /// let encoded = Base16384Utf8::encode_wrapped(b"12345678", wrap);
+    /// assert_eq!(encoded, "婌焳廔萷\r\n尀㴁");
+    /// ```
+    ///
+    /// [`String`]: alloc::string::String
+    pub fn encode_wrapped(data: &[u8], wrap: LineWrap) -> alloc::string::String {
+        let encoded = Self::encode(data);
+        let chars = encoded.len() / 3;
+        let separator = wrap.line_ending.as_str();
+        let mut result = alloc::string::String::with_capacity(
+            encoded.len() + wrap.separators(chars) * separator.len(),
+        );
+        // Every character takes 3 bytes, so the lines are on character boundaries.
+        let width = wrap.chars_per_line.saturating_mul(3);
+        let mut rest = encoded.as_str();
+        while rest.len() > width {
+            let (line, tail) = rest.split_at(width);
+            result.push_str(line);
+            result.push_str(separator);
+            rest = tail;
+        }
+        result.push_str(rest);
+        result
+    }
+}
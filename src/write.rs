@@ -0,0 +1,375 @@
+//! Streaming adapters implementing [`std::io::Write`].
+
+use std::io::{self, Write};
+use std::marker::PhantomData;
+
+use crate::error::Base16384DecodeError;
+use crate::read::invalid_data;
+use crate::stream::Repr;
+use crate::utils;
+use crate::utils::Scratch;
+use crate::{Base16384, Base16384Utf8};
+
+/// The number of chunks encoded before the output is handed to the inner writer.
+const BATCH: usize = 128;
+
+/// The size of the input buffer of decoders, in bytes.
+const BUF_SIZE: usize = 1024;
+
+/// Buffers `data` into 7-byte chunks, and writes the encoded full chunks to `inner`.
+///
+/// Returns the number of bytes consumed. Nothing is committed to `chunk` and `len`
+/// unless the inner write succeeds.
+fn write_chunks<W: Write, C: Repr>(
+    inner: &mut W,
+    chunk: &mut [u8; 7],
+    len: &mut usize,
+    data: &[u8],
+) -> io::Result<usize> {
+    if data.is_empty() {
+        return Ok(0);
+    }
+
+    let mut pending = *chunk;
+    let mut pending_len = *len;
+    let mut out = [0u8; BATCH * 12];
+    let mut i = 0;
+    let mut consumed = 0;
+
+    if pending_len > 0 {
+        let n = data.len().min(7 - pending_len);
+        pending[pending_len..pending_len + n].copy_from_slice(&data[..n]);
+        pending_len += n;
+        consumed += n;
+        if pending_len < 7 {
+            *chunk = pending;
+            *len = pending_len;
+            return Ok(consumed);
+        }
+        C::encode_chunk(&pending, &mut out[i..i + C::CHUNK]);
+        i += C::CHUNK;
+        pending_len = 0;
+    }
+
+    // SAFETY: 7 is non-zero.
+    let (chunks, remainder) = unsafe { utils::slice_as_chunks(&data[consumed..]) };
+    let room = BATCH - i / C::CHUNK;
+    for c in chunks.iter().take(room) {
+        C::encode_chunk(c, &mut out[i..i + C::CHUNK]);
+        i += C::CHUNK;
+        consumed += 7;
+    }
+    if chunks.len() <= room {
+        pending[..remainder.len()].copy_from_slice(remainder);
+        pending_len = remainder.len();
+        consumed += remainder.len();
+    }
+
+    inner.write_all(&out[..i])?;
+    *chunk = pending;
+    *len = pending_len;
+    Ok(consumed)
+}
+
+/// Encodes data written to it as Base16384 in UTF-16BE, and writes the result to
+/// the inner writer.
+///
+/// Partial 7-byte chunks are buffered across calls to [`write`](Write::write). Call
+/// [`finish`](Base16384Encoder::finish) after the last write to flush the remainder
+/// and the padding code point. Dropping the encoder finishes it as well, but errors
+/// are ignored then.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// use base16384::Base16384Encoder;
+///
+/// let mut encoder = Base16384Encoder::new(Vec::new());
+/// encoder.write_all(b"1234").unwrap();
+/// encoder.write_all(b"5678").unwrap();
+/// let encoded = encoder.finish().unwrap();
+///
+/// let text = "婌焳廔萷尀㴁"
+///     .encode_utf16()
+///     .flat_map(u16::to_be_bytes)
+///     .collect::<Vec<_>>();
+/// assert_eq!(encoded, text);
+/// ```
+pub type Base16384Encoder<W> = EncodingWriter<W, Base16384>;
+
+/// Encodes data written to it as Base16384 in UTF-8, and writes the result to the
+/// inner writer.
+///
+/// This is the UTF-8 counterpart of [`Base16384Encoder`]. Call
+/// [`finish`](Base16384Utf8Encoder::finish) after the last write.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// use base16384::Base16384Utf8Encoder;
+///
+/// let mut encoder = Base16384Utf8Encoder::new(Vec::new());
+/// encoder.write_all(b"1234").unwrap();
+/// encoder.write_all(b"5678").unwrap();
+/// let encoded = encoder.finish().unwrap();
+///
+/// assert_eq!(encoded, "婌焳廔萷尀㴁".as_bytes());
+/// ```
+pub type Base16384Utf8Encoder<W> = EncodingWriter<W, Base16384Utf8>;
+
+/// Encodes data written to it as Base16384, and writes the result to the inner
+/// writer.
+///
+/// Use it as [`Base16384Encoder`] for UTF-16BE, or as [`Base16384Utf8Encoder`] for
+/// UTF-8.
+pub struct EncodingWriter<W: Write, C: Repr> {
+    inner: Option<W>,
+    chunk: [u8; 7],
+    len: usize,
+    repr: PhantomData<C>,
+}
+
+impl<W: Write, C: Repr> EncodingWriter<W, C> {
+    /// Creates a new encoder writing to the given writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: Some(inner),
+            chunk: [0; 7],
+            len: 0,
+            repr: PhantomData,
+        }
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("encoder is finished")
+    }
+
+    /// Gets a mutable reference to the inner writer.
+    ///
+    /// Writing to the inner writer directly will corrupt the encoded output.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect("encoder is finished")
+    }
+
+    /// Writes the buffered remainder and the padding code point, and returns the
+    /// inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_remainder()?;
+        Ok(self.inner.take().expect("encoder is finished"))
+    }
+
+    fn write_remainder(&mut self) -> io::Result<()> {
+        if self.len == 0 {
+            return Ok(());
+        }
+        let inner = self.inner.as_mut().expect("encoder is finished");
+
+        let mut out = [0u8; 15];
+        let n = C::encode_tail(&self.chunk[..self.len], &mut out);
+        inner.write_all(&out[..n])?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write, C: Repr> Write for EncodingWriter<W, C> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let inner = self.inner.as_mut().expect("encoder is finished");
+        write_chunks::<W, C>(inner, &mut self.chunk, &mut self.len, data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write, C: Repr> Drop for EncodingWriter<W, C> {
+    fn drop(&mut self) {
+        if self.inner.is_some() && !std::thread::panicking() {
+            let _ = self.write_remainder();
+        }
+    }
+}
+
+/// Decodes Base16384 in UTF-16BE written to it, and writes the decoded bytes to the
+/// inner writer.
+///
+/// This is the inverse of [`Base16384Encoder`]. Since a chunk can only be decoded
+/// once the code unit after it is known, decoded bytes reach the inner writer with
+/// some delay; [`flush`](Write::flush) pushes out everything decodable so far. Call
+/// [`finish`](DecoderWriter::finish) after the last write to decode the last chunk.
+///
+/// Decoding errors are returned as [`io::Error`]s of kind [`InvalidData`], wrapping a
+/// [`Base16384DecodeError`]. The index of an invalid character is the index of the
+/// code unit in the whole stream.
+///
+/// [`InvalidData`]: io::ErrorKind::InvalidData
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// use base16384::write::DecoderWriter;
+///
+/// let text = "婌焳廔萷尀㴁"
+///     .encode_utf16()
+///     .flat_map(u16::to_be_bytes)
+///     .collect::<Vec<_>>();
+/// let mut decoder = DecoderWriter::new(Vec::new());
+/// decoder.write_all(&text).unwrap();
+/// let decoded = decoder.finish().unwrap();
+/// assert_eq!(decoded, b"12345678");
+/// ```
+pub type DecoderWriter<W> = DecodingWriter<W, Base16384>;
+
+/// Decodes Base16384 in UTF-8 written to it, and writes the decoded bytes to the
+/// inner writer.
+///
+/// This is the UTF-8 counterpart of [`DecoderWriter`]. The index of an invalid
+/// character is the byte index in the whole stream.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// use base16384::write::Utf8DecoderWriter;
+///
+/// let mut decoder = Utf8DecoderWriter::new(Vec::new());
+/// decoder.write_all("婌焳廔萷尀㴁".as_bytes()).unwrap();
+/// let decoded = decoder.finish().unwrap();
+/// assert_eq!(decoded, b"12345678");
+/// ```
+pub type Utf8DecoderWriter<W> = DecodingWriter<W, Base16384Utf8>;
+
+/// Decodes Base16384 written to it, and writes the decoded bytes to the inner
+/// writer.
+///
+/// Use it as [`DecoderWriter`] for UTF-16BE, or as [`Utf8DecoderWriter`] for UTF-8.
+pub struct DecodingWriter<W: Write, C: Repr> {
+    inner: Option<W>,
+    buf: [u8; BUF_SIZE],
+    start: usize,
+    end: usize,
+    position: usize,
+    repr: PhantomData<C>,
+}
+
+impl<W: Write, C: Repr> DecodingWriter<W, C> {
+    /// Creates a new decoder writing to the given writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: Some(inner),
+            buf: [0; BUF_SIZE],
+            start: 0,
+            end: 0,
+            position: 0,
+            repr: PhantomData,
+        }
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("decoder is finished")
+    }
+
+    /// Gets a mutable reference to the inner writer.
+    ///
+    /// Writing to the inner writer directly will corrupt the decoded output.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect("decoder is finished")
+    }
+
+    /// Decodes the last chunk, and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_remainder()?;
+        Ok(self.inner.take().expect("decoder is finished"))
+    }
+
+    #[inline]
+    fn available(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the next 4 characters form a full chunk.
+    #[inline]
+    fn chunk_ready(&self) -> bool {
+        self.available() >= C::CHUNK + C::CHAR && !C::is_padding(&self.buf[self.start + C::CHUNK..])
+    }
+
+    /// Decodes all full chunks in the buffer, and writes them to the inner writer.
+    ///
+    /// `eof` tells whether no more data will be written.
+    fn write_ready(&mut self, eof: bool) -> io::Result<()> {
+        let (start, position) = (self.start, self.position);
+        let mut out = [0u8; BUF_SIZE / 8 * 7];
+        let mut n = 0;
+        let mut result = Ok(());
+        while self.chunk_ready() {
+            let data = &self.buf[self.start..self.end];
+            let buf = (&mut out[n..n + 7]).try_into().unwrap();
+            if let Err(err) = C::decode_chunk(data, eof, buf) {
+                result = Err(err.offset(self.position));
+                break;
+            }
+            n += 7;
+            self.start += C::CHUNK;
+            self.position += C::CHUNK / C::INDEX;
+        }
+        if result.is_ok() && self.available() / C::INDEX > (C::CHUNK + C::CHAR) / C::INDEX {
+            // The padding code point is followed by more data.
+            result = Err(Base16384DecodeError::PaddingNotAtEnd {
+                index: self.position + C::CHUNK / C::INDEX,
+            });
+        }
+
+        if let Err(err) = self.get_mut().write_all(&out[..n]) {
+            self.start = start;
+            self.position = position;
+            return Err(err);
+        }
+        result.map_err(invalid_data)
+    }
+
+    fn write_remainder(&mut self) -> io::Result<()> {
+        self.write_ready(true)?;
+        let mut out = Scratch::new([0u8; 7]);
+        let decoded = C::decode_last_chunk(&self.buf[self.start..self.end], &mut out)
+            .map_err(|e| invalid_data(e.offset(self.position)))?;
+        self.inner
+            .as_mut()
+            .expect("decoder is finished")
+            .write_all(decoded)?;
+        self.position += self.available() / C::INDEX;
+        self.start = self.end;
+        Ok(())
+    }
+}
+
+impl<W: Write, C: Repr> Write for DecodingWriter<W, C> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+        self.write_ready(false)?;
+
+        self.buf.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+        let n = data.len().min(BUF_SIZE - self.end);
+        self.buf[self.end..self.end + n].copy_from_slice(&data[..n]);
+        self.end += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_ready(false)?;
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write, C: Repr> Drop for DecodingWriter<W, C> {
+    fn drop(&mut self) {
+        if self.inner.is_some() && !std::thread::panicking() {
+            let _ = self.write_remainder();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn encode_into() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let mut encoded = Vec::new();
+    let mut text = String::new();
+    for len in 0..40 {
+        encoded.clear();
+        Base16384::encode_into_vec(&data[..len], &mut encoded);
+        assert_eq!(encoded, Base16384::encode(&data[..len]));
+
+        text.clear();
+        Base16384Utf8::encode_into_string(&data[..len], &mut text);
+        assert_eq!(text, Base16384Utf8::encode(&data[..len]));
+    }
+
+    let mut encoded = Vec::new();
+    Base16384::encode_into_vec(&data, &mut encoded);
+    assert_eq!(encoded.capacity(), Base16384::encode_len(data.len()));
+}
+
+#[test]
+fn decode_into() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let mut decoded = b"x".to_vec();
+    for len in 0..40 {
+        decoded.truncate(1);
+        Base16384::decode_into_vec(&Base16384::encode(&data[..len]), &mut decoded).unwrap();
+        assert_eq!(decoded[1..], data[..len]);
+
+        decoded.truncate(1);
+        Base16384Utf8::decode_into_vec(&Base16384Utf8::encode(&data[..len]), &mut decoded).unwrap();
+        assert_eq!(decoded[1..], data[..len]);
+    }
+}
+
+#[test]
+fn decode_into_error() {
+    let mut decoded = b"abc".to_vec();
+    let mut encoded = Base16384::encode(&[0u8; 20]);
+    encoded[9] = 0;
+    assert!(Base16384::decode_into_vec(&encoded, &mut decoded).is_err());
+    assert!(Base16384::decode_into_vec(&[0x3d01], &mut decoded).is_err());
+    assert!(Base16384Utf8::decode_into_vec("㴁", &mut decoded).is_err());
+    assert_eq!(decoded, b"abc");
+}
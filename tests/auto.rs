@@ -0,0 +1,70 @@
+use base16384::auto::{decode_auto, sniff, Representation};
+use base16384::error::Base16384DecodeError;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn decode_any() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let data = &data[..len];
+        let utf8 = Base16384Utf8::encode(data);
+        let with_bom = ["\u{FEFF}", &utf8].concat();
+        assert_eq!(
+            decode_auto(utf8.as_bytes()).unwrap(),
+            (data.to_vec(), Representation::Utf8)
+        );
+        assert_eq!(
+            decode_auto(with_bom.as_bytes()).unwrap(),
+            (data.to_vec(), Representation::Utf8)
+        );
+
+        for bom in [false, true] {
+            let be = Base16384::encode_utf16be(data, bom);
+            let le = Base16384::encode_utf16le(data, bom);
+            if len > 0 || bom {
+                assert_eq!(
+                    decode_auto(&be).unwrap(),
+                    (data.to_vec(), Representation::Utf16Be)
+                );
+                assert_eq!(
+                    decode_auto(&le).unwrap(),
+                    (data.to_vec(), Representation::Utf16Le)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn sniff_ambiguous() {
+    assert_eq!(sniff(b""), (Representation::Utf8, 0));
+    assert_eq!(sniff(b"\xEF\xBB\xBF"), (Representation::Utf8, 3));
+    // Neither pattern matches, so fall back to UTF-16BE like the C tool.
+    assert_eq!(sniff(b"hello!"), (Representation::Utf16Be, 0));
+}
+
+#[test]
+fn decode_auto_error() {
+    let mut utf8 = b"\xEF\xBB\xBF".to_vec();
+    utf8.extend_from_slice(Base16384Utf8::encode(b"1234567").as_bytes());
+    utf8[4] = 0xFF;
+    assert_eq!(
+        decode_auto(&utf8),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 3,
+            value: 0xfffd,
+            padding_position: false,
+        })
+    );
+
+    let mut be = Base16384::encode_utf16be(b"12345678", true);
+    be[4] = 0x20;
+    assert_eq!(
+        decode_auto(&be),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 4,
+            value: 0x2033,
+            padding_position: false,
+        })
+    );
+}
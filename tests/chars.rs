@@ -0,0 +1,42 @@
+use base16384::error::Base16384DecodeError;
+use base16384::Base16384;
+
+#[test]
+fn encode_chars() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let expected = String::from_utf16(&Base16384::encode(&data[..len])).unwrap();
+        assert_eq!(
+            Base16384::encode_chars(&data[..len]).collect::<String>(),
+            expected
+        );
+
+        let mut buf = vec!['\0'; Base16384::encode_len(len)];
+        let encoded = Base16384::encode_to_char_slice(&data[..len], &mut buf);
+        assert_eq!(encoded.iter().collect::<String>(), expected);
+    }
+}
+
+#[test]
+fn decode_chars() {
+    let data = (0..=255).chain([0xFF; 7]).collect::<Vec<u8>>();
+    let decoded = Base16384::decode_chars(Base16384::encode_chars(&data));
+    assert_eq!(decoded.collect::<Result<Vec<_>, _>>().unwrap(), data);
+}
+
+#[test]
+fn decode_chars_errors() {
+    for c in ['a', '\u{4DFF}', '\u{8E00}', '\u{14E00}'] {
+        let mut chars = Base16384::encode_chars(&[0u8; 14]).collect::<Vec<_>>();
+        chars[5] = c;
+        let decoded = Base16384::decode_chars(chars);
+        assert_eq!(
+            decoded.collect::<Result<Vec<_>, _>>(),
+            Err(Base16384DecodeError::InvalidCharacter {
+                index: 5,
+                value: u16::try_from(c as u32).unwrap_or(u16::MAX) as u32,
+                padding_position: false,
+            })
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use base16384::error::{Base16384DecodeError, Base16384EncodeError};
+use base16384::{Base16384, Base16384Utf8, Codec};
+
+fn roundtrip<C: Codec>() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let data = &data[..len];
+        let mut encoded = vec![C::Unit::default(); C::encode_len(data.len())];
+        let encoded = C::encode_to_slice(data, &mut encoded);
+        assert_eq!(encoded.len(), C::encode_len(data.len()));
+        assert_eq!(encoded.len() % C::CHAR_LEN, 0);
+
+        let padding = C::trailing_padding(encoded);
+        let needed = C::try_decode_len(encoded.len(), padding).unwrap();
+        assert_eq!(needed, C::decode_len(encoded.len(), padding));
+        let mut decoded = vec![0u8; needed];
+        assert_eq!(C::decode_to_slice(encoded, &mut decoded).unwrap(), data);
+    }
+}
+
+#[test]
+fn codec_roundtrip() {
+    roundtrip::<Base16384>();
+    roundtrip::<Base16384Utf8>();
+}
+
+fn buffer_too_small<C: Codec>() {
+    let mut encoded = vec![C::Unit::default(); C::encode_len(8)];
+    let err = C::try_encode_to_slice(b"12345678", &mut encoded[1..]).unwrap_err();
+    assert_eq!(
+        err,
+        Base16384EncodeError::BufferTooSmall {
+            needed: encoded.len(),
+            available: encoded.len() - 1,
+        }
+    );
+
+    let encoded = C::try_encode_to_slice(b"12345678", &mut encoded).unwrap();
+    let mut decoded = [0u8; 7];
+    #[allow(deprecated)]
+    let err = C::try_decode_to_slice(encoded, &mut decoded).unwrap_err();
+    assert_eq!(
+        err,
+        Base16384DecodeError::BufferTooSmall {
+            needed: 8,
+            available: 7,
+        }
+    );
+}
+
+#[test]
+fn codec_buffer_too_small() {
+    buffer_too_small::<Base16384>();
+    buffer_too_small::<Base16384Utf8>();
+}
+
+#[test]
+fn codec_short_padding() {
+    // The padding code point claims a longer last chunk than the data has.
+    let mut buf = [0u8; 16];
+    assert_eq!(
+        Base16384::decode_to_slice(&[0x3d06], &mut buf),
+        Err(Base16384DecodeError::InvalidPadding {
+            index: 0,
+            value: 0x3d06,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::decode_to_slice("㴆", &mut buf),
+        Err(Base16384DecodeError::InvalidPadding {
+            index: 0,
+            value: 0x3d06,
+        })
+    );
+    assert_eq!(
+        <Base16384Utf8 as Codec>::decode_to_slice(&[0xFF; 6], &mut buf),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 0,
+            value: 0xfffd,
+            padding_position: false,
+        })
+    );
+}
@@ -0,0 +1,213 @@
+use base16384::config::{Config, DecodePadding};
+use base16384::error::Base16384DecodeError;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn padded_roundtrip() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let config = Config::default();
+    let indifferent = config.with_decode_padding(DecodePadding::Indifferent);
+    for len in 0..40 {
+        let data = &data[..len];
+        let encoded = Base16384::encode_with_config(data, config);
+        assert_eq!(encoded, Base16384::encode(data));
+        assert_eq!(
+            Base16384::decode_with_config(&encoded, config).unwrap(),
+            data
+        );
+        assert_eq!(
+            Base16384::decode_with_config(&encoded, indifferent).unwrap(),
+            data
+        );
+
+        let encoded = Base16384Utf8::encode_with_config(data, config);
+        assert_eq!(encoded, Base16384Utf8::encode(data));
+        assert_eq!(
+            Base16384Utf8::decode_with_config(&encoded, config).unwrap(),
+            data
+        );
+        assert_eq!(
+            Base16384Utf8::decode_with_config(&encoded, indifferent).unwrap(),
+            data
+        );
+    }
+}
+
+#[test]
+fn unpadded_roundtrip() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let config = Config::new().with_encode_padding(false);
+    for len in 0..40 {
+        let data = &data[..len];
+        let encoded = Base16384::encode_with_config(data, config);
+        let padded = Base16384::encode(data);
+        assert_eq!(encoded, padded[..encoded.len()]);
+        assert_eq!(encoded.len(), padded.len() - (len % 7 != 0) as usize);
+
+        let unpadded = config.with_decode_padding(DecodePadding::Unpadded(len));
+        assert_eq!(
+            Base16384::decode_with_config(&encoded, unpadded).unwrap(),
+            data
+        );
+
+        // Without the length, the last chunk decodes to all its whole bytes.
+        let indifferent = config.with_decode_padding(DecodePadding::Indifferent);
+        let decoded = Base16384::decode_with_config(&encoded, indifferent).unwrap();
+        assert_eq!(decoded[..len], *data);
+        assert!(decoded[len..].iter().all(|&b| b == 0));
+
+        let encoded = Base16384Utf8::encode_with_config(data, config);
+        assert_eq!(
+            Base16384Utf8::decode_with_config(&encoded, unpadded).unwrap(),
+            data
+        );
+        let decoded = Base16384Utf8::decode_with_config(&encoded, indifferent).unwrap();
+        assert_eq!(decoded[..len], *data);
+    }
+}
+
+#[test]
+fn padding_errors() {
+    let padded = Base16384::encode(b"12345678");
+    let unpadded =
+        Base16384::encode_with_config(b"12345678", Config::new().with_encode_padding(false));
+
+    let required = Config::new();
+    assert_eq!(
+        Base16384::decode_with_config(&unpadded, required),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+
+    let config = Config::new().with_decode_padding(DecodePadding::Unpadded(8));
+    assert_eq!(
+        Base16384::decode_with_config(&padded, config),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 5,
+            value: 0x3d01,
+            padding_position: true,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::decode_with_config("婌焳廔萷尀㴁", config),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 15,
+            value: 0x3d01,
+            padding_position: true,
+        })
+    );
+
+    // The length supplied out of band does not match the data.
+    let config = Config::new().with_decode_padding(DecodePadding::Unpadded(10));
+    assert_eq!(
+        Base16384::decode_with_config(&unpadded, config),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+    assert_eq!(
+        Base16384Utf8::decode_with_config("婌焳廔萷尀", config),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+}
+
+#[test]
+fn strict() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let strict = Config::new().with_strict(true);
+    for len in 0..40 {
+        let data = &data[..len];
+        let encoded = Base16384::encode(data);
+        assert_eq!(
+            Base16384::decode_with_config(&encoded, strict).unwrap(),
+            data
+        );
+        let text = Base16384Utf8::encode(data);
+        assert_eq!(
+            Base16384Utf8::decode_with_config(&text, strict).unwrap(),
+            data
+        );
+
+        let unpadded = Base16384::encode_with_config(data, strict.with_encode_padding(false));
+        let indifferent = strict.with_decode_padding(DecodePadding::Indifferent);
+        let unpadded_len = strict.with_decode_padding(DecodePadding::Unpadded(len));
+        assert_eq!(
+            Base16384::decode_with_config(&unpadded, unpadded_len).unwrap(),
+            data
+        );
+        if unpadded.len() % 4 != 0 {
+            // Without padding, a partial last chunk is not canonical.
+            assert_eq!(
+                Base16384::decode_with_config(&unpadded, indifferent),
+                Err(Base16384DecodeError::NonCanonical {
+                    index: unpadded.len() - 1
+                })
+            );
+        }
+
+        if len % 7 == 0 {
+            continue;
+        }
+        // The lowest bit of the last character before the padding is always discarded.
+        let mut encoded = encoded;
+        let index = encoded.len() - 2;
+        encoded[index] ^= 1;
+        assert_eq!(
+            Base16384::decode_with_config(&encoded, Config::new()).unwrap(),
+            data
+        );
+        assert_eq!(
+            Base16384::decode_with_config(&encoded, strict),
+            Err(Base16384DecodeError::NonCanonical { index })
+        );
+
+        let text = String::from_utf16(&encoded).unwrap();
+        assert_eq!(
+            Base16384Utf8::decode_with_config(&text, strict),
+            Err(Base16384DecodeError::NonCanonical { index: index * 3 })
+        );
+    }
+}
+
+#[test]
+fn strict_empty_padding() {
+    let strict = Config::new().with_strict(true);
+    let mut encoded = Base16384::encode(b"1234567");
+    encoded.push(0x3d00);
+    assert_eq!(
+        Base16384::decode_with_config(&encoded, Config::new()).unwrap(),
+        b"1234567"
+    );
+    assert_eq!(
+        Base16384::decode_with_config(&encoded, strict),
+        Err(Base16384DecodeError::NonCanonical { index: 4 })
+    );
+    assert_eq!(
+        Base16384Utf8::decode_with_config("婌焳廔萳㴀", strict),
+        Err(Base16384DecodeError::NonCanonical { index: 12 })
+    );
+}
+
+#[test]
+fn strict_indifferent() {
+    let indifferent = Config::new()
+        .with_strict(true)
+        .with_decode_padding(DecodePadding::Indifferent);
+    assert_eq!(
+        Base16384Utf8::decode_with_config("婌焳廔萷尀㴁", indifferent).unwrap(),
+        b"12345678"
+    );
+    assert_eq!(
+        Base16384Utf8::decode_with_config("婌焳廔萷尀", indifferent),
+        Err(Base16384DecodeError::NonCanonical { index: 12 })
+    );
+
+    // 2 bytes take 2 code units, which decode to 3 bytes without padding.
+    let encoded = Base16384::encode_with_config(b"ab", Config::new().with_encode_padding(false));
+    assert_eq!(encoded.len(), 2);
+    assert_eq!(
+        Base16384::decode_with_config(&encoded, indifferent.with_strict(false)).unwrap(),
+        [b'a', b'b', 0]
+    );
+    assert_eq!(
+        Base16384::decode_with_config(&encoded, indifferent),
+        Err(Base16384DecodeError::NonCanonical { index: 1 })
+    );
+}
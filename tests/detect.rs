@@ -0,0 +1,80 @@
+use base16384::auto::Representation;
+use base16384::detect::{detect, detect_bytes};
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn detect_encoded() {
+    let data = (0..=255)
+        .map(|i: u8| i.wrapping_mul(97))
+        .collect::<Vec<u8>>();
+    for len in 1..100 {
+        let text = Base16384Utf8::encode(&data[..len]);
+        let detection = detect(&text);
+        assert_eq!(detection.representation, Representation::Utf8);
+        assert_eq!(detection.payload_len, Some(len));
+        if len >= 14 {
+            assert!(detection.confidence > 0.5, "{}", len);
+        }
+    }
+    let text = Base16384Utf8::encode(&data);
+    assert!(detect(&text).confidence > 0.9);
+}
+
+#[test]
+fn detect_bytes_encoded() {
+    let data = b"some binary data, e.g. a key";
+    for (encoded, representation) in [
+        (
+            Base16384::encode_utf16be(data, false),
+            Representation::Utf16Be,
+        ),
+        (
+            Base16384::encode_utf16le(data, true),
+            Representation::Utf16Le,
+        ),
+        (
+            Base16384Utf8::encode(data).into_bytes(),
+            Representation::Utf8,
+        ),
+    ] {
+        let detection = detect_bytes(&encoded);
+        assert_eq!(detection.representation, representation);
+        assert_eq!(detection.payload_len, Some(data.len()));
+        assert!(detection.confidence > 0.8);
+    }
+}
+
+#[test]
+fn detect_invalid() {
+    assert_eq!(detect("").payload_len, Some(0));
+    assert_eq!(detect("").confidence, 0.0);
+
+    // Outside the alphabet.
+    assert_eq!(detect("婌焳廔萷尀x").confidence, 0.0);
+    assert_eq!(detect("婌焳廔萷龥").confidence, 0.0);
+    // Padding before the end.
+    assert_eq!(detect("婌焳廔萷尀㴁尀").confidence, 0.0);
+    assert_eq!(detect("婌焳㴁廔萷尀㴁").confidence, 0.0);
+
+    // Inconsistent length or padding.
+    let detection = detect("婌焳廔萷尀㴂");
+    assert_eq!(detection.payload_len, None);
+    assert!(detection.confidence < 0.3);
+    let detection = detect("婌焳廔萷尀");
+    assert_eq!(detection.payload_len, None);
+    assert!(detection.confidence < 0.3);
+    // Not canonical.
+    assert_eq!(detect("婌焳廔萷封㴁").payload_len, None);
+    assert_eq!(detect("婌焳廔萷尀㴁").payload_len, Some(8));
+}
+
+#[test]
+fn detect_prose() {
+    for text in [
+        "我们需要一个新的方案来处理这个问题",
+        "他说这个国家的人民生活得很好",
+    ] {
+        let detection = detect(text);
+        assert!(detection.confidence < 0.2, "{}: {:?}", text, detection);
+    }
+}
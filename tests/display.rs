@@ -0,0 +1,46 @@
+use core::fmt::{self, Write};
+
+use base16384::{Base16384Display, Base16384Utf8};
+
+/// A fixed-capacity sink, standing in for `heapless::String`.
+struct Sink<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Write for Sink<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let buf = self
+            .buf
+            .get_mut(self.len..self.len + s.len())
+            .ok_or(fmt::Error)?;
+        buf.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+#[test]
+fn display() {
+    let data = (0..2000).map(|i| (i * 31 % 251) as u8).collect::<Vec<_>>();
+    for len in (0..40).chain([447, 448, 449, 2000]) {
+        let expected = Base16384Utf8::encode(&data[..len]);
+        assert_eq!(Base16384Display(&data[..len]).to_string(), expected);
+    }
+}
+
+#[test]
+fn encode_to_fmt() {
+    let mut sink = Sink {
+        buf: [0u8; 18],
+        len: 0,
+    };
+    Base16384Utf8::encode_to_fmt(b"12345678", &mut sink).unwrap();
+    assert_eq!(&sink.buf[..sink.len], "婌焳廔萷尀㴁".as_bytes());
+
+    let mut sink = Sink {
+        buf: [0u8; 17],
+        len: 0,
+    };
+    assert!(Base16384Utf8::encode_to_fmt(b"12345678", &mut sink).is_err());
+}
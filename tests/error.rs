@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use base16384::codec::Codec;
+use base16384::error::Base16384DecodeError;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn error_absolute_index() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let encoded = Base16384::encode(&data[..30]);
+    let mut buf = [0u8; 32];
+    for i in 0..encoded.len() - 1 {
+        let mut damaged = encoded.clone();
+        damaged[i] = b'x' as u16;
+        let expected = Base16384DecodeError::InvalidCharacter {
+            index: i,
+            value: 'x' as u32,
+            padding_position: false,
+        };
+        assert_eq!(Base16384::decode(&damaged).unwrap_err(), expected);
+        let err = Base16384::decode_to_slice(&damaged, &mut buf).unwrap_err();
+        assert_eq!(err, expected);
+
+        let text = String::from_utf16(&damaged).unwrap();
+        let index = text.char_indices().nth(i).unwrap().0;
+        let expected = Base16384DecodeError::InvalidCharacter {
+            index,
+            value: 'x' as u32,
+            padding_position: false,
+        };
+        assert_eq!(Base16384Utf8::decode(&text).unwrap_err(), expected);
+        let err = Base16384Utf8::decode_to_slice(&text, &mut buf).unwrap_err();
+        assert_eq!(err, expected);
+    }
+}
+
+#[test]
+fn error_padding() {
+    // The last character stands where the padding code point should be.
+    let mut data = "婌焳廔萷尀".encode_utf16().collect::<Vec<_>>();
+    data.push(0x4dff);
+    assert_eq!(
+        Base16384::decode(&data),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 5,
+            value: 0x4dff,
+            padding_position: true,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::decode("婌焳廔萷尀😀"),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 15,
+            value: '😀' as u32,
+            padding_position: true,
+        })
+    );
+
+    assert_eq!(
+        Base16384Utf8::decode("婌焳廔萷尀㴃"),
+        Err(Base16384DecodeError::InvalidPadding {
+            index: 15,
+            value: 0x3d03,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::decode("婌焳㴁萷尀㴁"),
+        Err(Base16384DecodeError::PaddingNotAtEnd { index: 6 })
+    );
+}
+
+#[test]
+fn error_truncated_character() {
+    let encoded = Base16384Utf8::encode(b"12345678").into_bytes();
+    let mut buf = [0u8; 8];
+    for cut in [1, 2] {
+        let data = &encoded[..encoded.len() - cut];
+        assert_eq!(
+            <Base16384Utf8 as Codec>::decode_to_slice(data, &mut buf),
+            Err(Base16384DecodeError::TruncatedCharacter { index: 15 })
+        );
+    }
+}
+
+#[test]
+fn error_display() {
+    let err = Base16384Utf8::decode("婌焳廔萷尀x").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid character U+0078 at index 15 in the padding position"
+    );
+    assert_eq!(err.index(), Some(15));
+    assert_eq!(
+        Base16384DecodeError::InvalidPadding {
+            index: 3,
+            value: 0x3d06
+        }
+        .to_string(),
+        "invalid padding U+3D06 at index 3"
+    );
+    assert_eq!(Base16384DecodeError::InvalidLength.index(), None);
+
+    let errors = [err.clone(), err, Base16384DecodeError::InvalidLength];
+    assert_eq!(errors.iter().collect::<HashSet<_>>().len(), 2);
+}
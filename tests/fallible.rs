@@ -0,0 +1,141 @@
+use base16384::config::Config;
+use base16384::error::{Base16384DecodeError, Base16384EncodeError};
+use base16384::state::DecoderState;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn try_encode_to_slice() {
+    let data = [0u8; 32];
+    let mut buf = [0u16; 20];
+    assert_eq!(
+        Base16384::try_encode_to_slice(&data, &mut buf[..19]),
+        Err(Base16384EncodeError::BufferTooSmall {
+            needed: 20,
+            available: 19
+        })
+    );
+    assert_eq!(
+        Base16384::try_encode_to_slice(&data, &mut buf).unwrap(),
+        Base16384::encode(&data)
+    );
+
+    let mut buf = "A".repeat(60);
+    assert_eq!(
+        Base16384Utf8::try_encode_to_slice(&data, &mut buf[..59]),
+        Err(Base16384EncodeError::BufferTooSmall {
+            needed: 60,
+            available: 59
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::try_encode_to_slice(&data, &mut buf).unwrap(),
+        Base16384Utf8::encode(&data)
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn try_decode_to_slice() {
+    let data = Base16384::encode(&[0u8; 32]);
+    let mut buf = [0u8; 32];
+    assert_eq!(
+        Base16384::try_decode_to_slice(&data, &mut buf[..31]),
+        Err(Base16384DecodeError::BufferTooSmall {
+            needed: 32,
+            available: 31
+        })
+    );
+    assert_eq!(
+        Base16384::try_decode_to_slice(&data, &mut buf).unwrap(),
+        [0u8; 32]
+    );
+
+    let data = Base16384Utf8::encode(&[0u8; 32]);
+    assert_eq!(
+        Base16384Utf8::try_decode_to_slice(&data, &mut buf[..31]),
+        Err(Base16384DecodeError::BufferTooSmall {
+            needed: 32,
+            available: 31
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::try_decode_to_slice(&data, &mut buf).unwrap(),
+        [0u8; 32]
+    );
+}
+
+#[test]
+fn decode_to_slice_buffer_too_small() {
+    let data = Base16384::encode(b"12345678");
+    let mut buf = [0u8; 8];
+    let config = Config::new().with_strict(true);
+    let err = Base16384DecodeError::BufferTooSmall {
+        needed: 8,
+        available: 7,
+    };
+    assert_eq!(
+        Base16384::decode_to_slice(&data, &mut buf[..7]),
+        Err(err.clone())
+    );
+    assert_eq!(
+        Base16384::decode_to_slice_with_config(&data, &mut buf[..7], config),
+        Err(err.clone())
+    );
+    let text = Base16384Utf8::encode(b"12345678");
+    assert_eq!(
+        Base16384Utf8::decode_to_slice(&text, &mut buf[..7]),
+        Err(err.clone())
+    );
+    assert_eq!(
+        Base16384Utf8::decode_to_slice_with_config(&text, &mut buf[..7], config),
+        Err(err)
+    );
+
+    // The state is kept, so finalizing can be retried with more room.
+    let mut state = DecoderState::new();
+    assert_eq!(state.update(&data, &mut buf), Ok((6, 7)));
+    assert_eq!(
+        state.finalize(&mut buf[7..7]),
+        Err(Base16384DecodeError::BufferTooSmall {
+            needed: 1,
+            available: 0
+        })
+    );
+    assert_eq!(state.finalize(&mut buf[7..]), Ok(1));
+    assert_eq!(buf, *b"12345678");
+}
+
+#[test]
+fn try_decode_invalid_length() {
+    let mut buf = [0u8; 32];
+    for (data, index, value) in [
+        (&[0x3d01][..], 0, 0x3d01),
+        (&[0x4e00, 0x4e00, 0x3d04], 2, 0x3d04),
+    ] {
+        assert_eq!(
+            Base16384::decode_to_slice(data, &mut buf),
+            Err(Base16384DecodeError::InvalidPadding { index, value })
+        );
+        let data = String::from_utf16(data).unwrap();
+        assert_eq!(
+            Base16384Utf8::decode_to_slice(&data, &mut buf),
+            Err(Base16384DecodeError::InvalidPadding {
+                index: index * 3,
+                value,
+            })
+        );
+    }
+    // Not a padding code point.
+    assert_eq!(
+        Base16384::decode_to_slice(&[0x4e00, 0x3d07], &mut buf),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 1,
+            value: 0x3d07,
+            padding_position: true,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::try_decode_len(4, None),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+}
@@ -0,0 +1,100 @@
+use base16384::config::Config;
+use base16384::inspect::{Problem, ProblemKind};
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn inspect_valid() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let encoded = Base16384::encode(&data[..len]);
+        let report = Base16384::inspect(&encoded);
+        assert!(report.is_valid(), "{}", report);
+        assert_eq!(report.payload_len, Some(len));
+        assert_eq!(report.chunks, len / 7);
+        assert_eq!(report.padding.is_some(), len % 7 != 0);
+
+        let encoded = Base16384Utf8::encode(&data[..len]);
+        assert_eq!(Base16384Utf8::inspect(&encoded), report);
+    }
+}
+
+#[test]
+fn inspect_matches_decode() {
+    let encoded = Base16384::encode(b"1234567812345");
+    let config = Config::new().with_strict(true);
+    for i in 0..encoded.len() {
+        for c in [
+            0x0041, 0x3D00, 0x3D01, 0x3D07, 0x4E00, 0x4E01, 0x8DFF, 0x8E00,
+        ] {
+            let mut data = encoded.clone();
+            data[i] = c;
+            for data in [&data[..], &data[..i + 1]] {
+                let report = Base16384::inspect(data);
+                let mut buf = [0u8; 16];
+                let decoded = Base16384::decode_to_slice_with_config(data, &mut buf, config);
+                assert_eq!(
+                    report.is_valid(),
+                    decoded.is_ok(),
+                    "{:x?}: {}",
+                    data,
+                    report
+                );
+                let lax = report
+                    .problems
+                    .iter()
+                    .all(|p| p.kind == ProblemKind::NonCanonical);
+                assert_eq!(lax, Base16384::validate(data).is_ok(), "{:x?}", data);
+                if lax {
+                    assert_eq!(report.payload_len, Base16384::validate(data).ok());
+                }
+
+                let text = String::from_utf16_lossy(data);
+                let report = Base16384Utf8::inspect(&text);
+                let decoded = Base16384Utf8::decode_with_config(&text, config);
+                assert_eq!(report.is_valid(), decoded.is_ok(), "{}: {}", text, report);
+            }
+        }
+    }
+}
+
+#[test]
+fn inspect_problems() {
+    let report = Base16384Utf8::inspect("婌焳😀萷尀㴁");
+    assert_eq!(
+        report.problems,
+        [Problem {
+            kind: ProblemKind::InvalidCharacter,
+            index: 6
+        }]
+    );
+    assert_eq!(report.payload_len, Some(8));
+
+    let report = Base16384Utf8::inspect("婌焳廔萷尀");
+    assert_eq!((report.chunks, report.remainder_len), (1, 1));
+    assert_eq!(report.payload_len, None);
+    assert_eq!(
+        report.problems,
+        [Problem {
+            kind: ProblemKind::InvalidLength,
+            index: 15
+        }]
+    );
+
+    let report = Base16384Utf8::inspect("婌焳廔萷㴀");
+    assert_eq!(report.payload_len, Some(7));
+    assert_eq!(
+        report.problems,
+        [Problem {
+            kind: ProblemKind::NonCanonical,
+            index: 12
+        }]
+    );
+
+    let report = Base16384::inspect(&[]);
+    assert!(report.is_valid());
+    assert_eq!(report.payload_len, Some(0));
+    assert_eq!(
+        report.to_string(),
+        "chunks: 0, remainder: 0, padding: none, payload: 0 bytes"
+    );
+}
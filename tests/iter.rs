@@ -0,0 +1,58 @@
+use base16384::error::Base16384DecodeError;
+use base16384::Base16384;
+
+#[test]
+fn encode_iter() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let encoded = Base16384::encode_iter(data[..len].iter().cloned());
+        assert_eq!(encoded.len(), Base16384::encode_len(len));
+        assert_eq!(encoded.collect::<Vec<_>>(), Base16384::encode(&data[..len]));
+    }
+
+    let mut encoded = Base16384::encode_iter(data.iter().cloned());
+    for len in (0..Base16384::encode_len(data.len())).rev() {
+        encoded.next().unwrap();
+        assert_eq!(encoded.len(), len);
+    }
+    assert_eq!(encoded.next(), None);
+}
+
+#[test]
+fn decode_iter() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let encoded = Base16384::encode(&data[..len]);
+        let decoded = Base16384::decode_iter(encoded.iter().cloned());
+        let (lower, upper) = decoded.size_hint();
+        assert!(lower <= len && len <= upper.unwrap());
+        assert_eq!(
+            decoded.collect::<Result<Vec<_>, _>>().unwrap(),
+            &data[..len]
+        );
+    }
+}
+
+#[test]
+fn decode_iter_errors() {
+    let mut encoded = Base16384::encode(&[0u8; 20]);
+    encoded[5] = 0;
+    let decoded = Base16384::decode_iter(encoded).collect::<Vec<_>>();
+    assert_eq!(decoded.len(), 8);
+    assert!(decoded[..7].iter().all(|b| *b == Ok(0)));
+    assert_eq!(
+        decoded[7],
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 5,
+            value: 0,
+            padding_position: false,
+        })
+    );
+
+    let encoded = Base16384::encode(&[0u8; 20]);
+    let decoded = Base16384::decode_iter(encoded[..encoded.len() - 2].iter().cloned());
+    assert_eq!(
+        decoded.last(),
+        Some(Err(Base16384DecodeError::InvalidLength))
+    );
+}
@@ -0,0 +1,111 @@
+use base16384::error::Base16384DecodeError;
+use base16384::lenient::DEFAULT_IGNORE;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn lenient_roundtrip() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let data = &data[..len];
+        let text = Base16384Utf8::encode(data);
+        let mut noisy = String::new();
+        for (i, c) in text.chars().enumerate() {
+            noisy.push(c);
+            noisy.push_str(["\n", " ", "\u{3000}", "\u{200B}", "\r\n", ""][i % 6]);
+        }
+
+        assert_eq!(
+            Base16384Utf8::decode_lenient(&noisy, DEFAULT_IGNORE).unwrap(),
+            data
+        );
+        let units = noisy.encode_utf16().collect::<Vec<_>>();
+        assert_eq!(
+            Base16384::decode_lenient(&units, DEFAULT_IGNORE).unwrap(),
+            data
+        );
+    }
+}
+
+#[test]
+fn lenient_ignore_set() {
+    assert_eq!(
+        Base16384Utf8::decode_lenient("婌焳-廔萷-尀㴁", &['-']).unwrap(),
+        b"12345678"
+    );
+    assert_eq!(
+        Base16384Utf8::decode_lenient("婌焳 廔萷尀㴁", &['-']),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 6,
+            value: ' ' as u32,
+            padding_position: false,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::decode_lenient("婌焳廔萷尀㴁", &[]).unwrap(),
+        b"12345678"
+    );
+
+    // Characters outside of the BMP are skipped in UTF-16 too.
+    let text = "婌焳😀廔萷尀😀㴁";
+    assert_eq!(
+        Base16384Utf8::decode_lenient(text, &['😀']).unwrap(),
+        b"12345678"
+    );
+    let units = text.encode_utf16().collect::<Vec<_>>();
+    assert_eq!(
+        Base16384::decode_lenient(&units, &['😀']).unwrap(),
+        b"12345678"
+    );
+    assert_eq!(
+        Base16384::decode_lenient(&units, DEFAULT_IGNORE),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 2,
+            value: 0xd83d,
+            padding_position: false,
+        })
+    );
+}
+
+#[test]
+fn lenient_errors() {
+    // The index counts the skipped characters.
+    let text = "婌 焳\n廔 萷 尀x㴂";
+    assert_eq!(
+        Base16384Utf8::decode_lenient(text, DEFAULT_IGNORE),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 19,
+            value: 'x' as u32,
+            padding_position: false,
+        })
+    );
+    let units = text.encode_utf16().collect::<Vec<_>>();
+    assert_eq!(
+        Base16384::decode_lenient(&units, DEFAULT_IGNORE),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 9,
+            value: 'x' as u32,
+            padding_position: false,
+        })
+    );
+
+    let text = "婌 焳\n廔 x 萷尀㴁";
+    assert_eq!(
+        Base16384Utf8::decode_lenient(text, DEFAULT_IGNORE),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 12,
+            value: 'x' as u32,
+            padding_position: false,
+        })
+    );
+
+    // Padding followed by more data.
+    assert_eq!(
+        Base16384Utf8::decode_lenient("尀㴁 \n尀㴁", DEFAULT_IGNORE),
+        Err(Base16384DecodeError::PaddingNotAtEnd { index: 3 })
+    );
+
+    assert_eq!(
+        Base16384Utf8::decode_lenient("婌 焳\n廔", DEFAULT_IGNORE),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+}
@@ -0,0 +1,141 @@
+use base16384::error::Base16384DecodeError;
+use base16384::lossy::LossyError;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn lossy_missing_padding() {
+    // The last partial chunk decodes to all its whole bytes, with zero bits past the
+    // data.
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let mut encoded = Base16384::encode(&data[..len]);
+        if len % 7 != 0 {
+            encoded.pop();
+        }
+        let output = Base16384::decode_lossy(&encoded, b'?');
+        assert_eq!(output.data[..len], data[..len]);
+        assert!(output.data[len..].iter().all(|&b| b == 0));
+        match &output.errors[..] {
+            [] => assert_eq!(Base16384::decode(&encoded).unwrap(), output.data),
+            [LossyError { error, range }] => {
+                assert_eq!(*error, Base16384DecodeError::InvalidLength);
+                assert_eq!(*range, len / 7 * 7..output.data.len());
+            }
+            errors => panic!("{:?}", errors),
+        }
+
+        let text = String::from_utf16(&encoded).unwrap();
+        assert_eq!(Base16384Utf8::decode_lossy(&text, b'?').data, output.data);
+    }
+}
+
+#[test]
+fn lossy_invalid_characters() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    let encoded = Base16384::encode(&data[..20]);
+    for i in 0..encoded.len() - 1 {
+        let mut damaged = encoded.clone();
+        damaged[i] = b'x' as u16;
+        let output = Base16384::decode_lossy(&damaged, 0xAA);
+        assert_eq!(output.data.len(), 20);
+        assert_eq!(output.errors.len(), 1);
+        let LossyError { error, range } = &output.errors[0];
+        assert_eq!(
+            *error,
+            Base16384DecodeError::InvalidCharacter {
+                index: i,
+                value: 'x' as u32,
+                padding_position: false,
+            }
+        );
+        assert!((2..=3).contains(&range.len()) || range.end == 20);
+        // The bits of the i-th character in its chunk.
+        let bits = i % 4 * 14..i % 4 * 14 + 14;
+        for (j, &b) in output.data.iter().enumerate() {
+            let mask = match j / 7 == i / 4 {
+                true => (0..8)
+                    .filter(|k| bits.contains(&(j % 7 * 8 + k)))
+                    .fold(0u8, |mask, k| mask | 0x80 >> k),
+                false => 0,
+            };
+            match mask {
+                0xFF => assert_eq!(b, 0xAA),
+                _ => assert_eq!(b, data[j] & !mask),
+            }
+            assert_eq!(mask != 0, range.contains(&j));
+        }
+    }
+}
+
+#[test]
+fn lossy_errors() {
+    let output = Base16384Utf8::decode_lossy("x焳廔萷婌焳廔😀尀㴁", b'?');
+    // Only the bits of the invalid characters are lost in the bytes shared with others.
+    assert_eq!(output.data, b"?\x023456712345\0?8");
+    assert_eq!(
+        output.errors,
+        [
+            LossyError {
+                error: Base16384DecodeError::InvalidCharacter {
+                    index: 0,
+                    value: 'x' as u32,
+                    padding_position: false,
+                },
+                range: 0..2,
+            },
+            LossyError {
+                error: Base16384DecodeError::InvalidCharacter {
+                    index: 19,
+                    value: '😀' as u32,
+                    padding_position: false,
+                },
+                range: 12..14,
+            },
+        ]
+    );
+
+    let output = Base16384Utf8::decode_lossy("婌焳廔㴁", 0);
+    assert_eq!(output.data, b"12345");
+    assert_eq!(
+        output.errors,
+        [LossyError {
+            error: Base16384DecodeError::InvalidPadding {
+                index: 9,
+                value: 0x3d01,
+            },
+            range: 0..5,
+        }]
+    );
+
+    // Padding in the middle is replaced too.
+    let output = Base16384Utf8::decode_lossy("婌焳㴁萷尀㴁", b'?');
+    assert_eq!(output.data, b"1230?678");
+    assert_eq!(
+        output.errors[0].error,
+        Base16384DecodeError::PaddingNotAtEnd { index: 6 }
+    );
+
+    // Without padding, the last character is in the padding position.
+    let output = Base16384::decode_lossy(&[0x5a4c, 0x7133, b'x' as u16], 0);
+    assert_eq!(
+        output.errors[0].error,
+        Base16384DecodeError::InvalidCharacter {
+            index: 2,
+            value: 'x' as u32,
+            padding_position: true,
+        }
+    );
+
+    let output = Base16384::decode_lossy(&[0x5a4c, 0x7133, 0x3d06], 0);
+    assert_eq!(output.data, b"123");
+    assert_eq!(
+        output.errors,
+        [LossyError {
+            error: Base16384DecodeError::InvalidPadding {
+                index: 2,
+                value: 0x3d06,
+            },
+            range: 0..3,
+        }]
+    );
+}
@@ -8,3 +8,12 @@ fn magic_1() {
     assert_eq!(decoded.len(), 256);
     assert_eq!(decoded, (0..=255).collect::<Vec<_>>());
 }
+
+#[test]
+fn ones_roundtrip() {
+    for len in 0..=21 {
+        let data = vec![0xFFu8; len];
+        let encoded = Base16384::encode(&data);
+        assert_eq!(Base16384::decode(&encoded).unwrap(), data);
+    }
+}
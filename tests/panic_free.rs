@@ -0,0 +1,206 @@
+//! Exhaustive checks that decoding never panics on small inputs, built from the code
+//! units, bytes and UTF-8 fragments at the edges of the alphabet and the padding code
+//! points.
+
+use std::io::{Read, Write};
+
+use base16384::auto::decode_auto;
+use base16384::codec::Codec;
+use base16384::config::{Config, DecodePadding};
+use base16384::detect::{detect, detect_bytes};
+use base16384::lenient::DEFAULT_IGNORE;
+use base16384::state::DecoderState;
+use base16384::write::{DecoderWriter, Utf8DecoderWriter};
+use base16384::{Base16384, Base16384Decoder, Base16384Utf8, Base16384Utf8Decoder};
+
+const UNITS: &[u16] = &[
+    0x0000, 0x0041, 0x3cff, 0x3d00, 0x3d01, 0x3d02, 0x3d03, 0x3d04, 0x3d05, 0x3d06, 0x3d07, 0x4dff,
+    0x4e00, 0x5a4c, 0x8dff, 0x8e00, 0xd800, 0xfeff,
+];
+
+/// Fewer code units for longer inputs, still covering every kind of character.
+const FEW_UNITS: &[u16] = &[0x3d00, 0x3d01, 0x3d06, 0x4dff, 0x4e00, 0xd800];
+
+const BYTES: &[u8] = &[
+    0x00, 0x41, 0x80, 0x84, 0xb4, 0xb8, 0xbf, 0xc3, 0xe3, 0xe4, 0xef, 0xf0, 0xff,
+];
+
+/// Fragments of UTF-8 for inputs longer than a chunk: characters of the alphabet, the
+/// padding code points of 1 and 6 bytes, a 4-byte character, and lone bytes that
+/// shift the following characters.
+const FRAGMENTS: &[&[u8]] = &[
+    b"\xe4\xb8\x80",
+    b"\xe3\xb4\x81",
+    b"\xe3\xb4\x86",
+    b"\xf0\x9f\x98\x80",
+    b"\xe4",
+    b"\x80",
+];
+
+fn configs() -> Vec<Config> {
+    let mut configs = vec![Config::new(), Config::new().with_strict(true)];
+    configs.push(Config::new().with_decode_padding(DecodePadding::Indifferent));
+    for len in [0, 1, 2, 6, 7, 8] {
+        configs.push(Config::new().with_decode_padding(DecodePadding::Unpadded(len)));
+    }
+    configs
+}
+
+/// Calls every `n`-tuple of `alphabet` with `f`.
+fn for_each_input<T: Copy>(alphabet: &[T], n: usize, f: &mut impl FnMut(&[T])) {
+    let mut input = vec![alphabet[0]; n];
+    let mut digits = vec![0; n];
+    loop {
+        for (c, &d) in input.iter_mut().zip(&digits) {
+            *c = alphabet[d];
+        }
+        f(&input);
+        let mut i = 0;
+        loop {
+            if i == n {
+                return;
+            }
+            digits[i] += 1;
+            if digits[i] < alphabet.len() {
+                break;
+            }
+            digits[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+fn decode_units(data: &[u16], configs: &[Config]) {
+    let mut buf = [0u8; 64];
+    let last = data.last().cloned();
+    let len = Base16384::decode_len(data.len(), last.and_then(Base16384::padding));
+    let _ = Base16384::decode_len(data.len(), last);
+    let _ = Base16384::try_decode_len(data.len(), last);
+    let _ = Base16384::decode(data);
+    let _ = Base16384::validate(data);
+    for n in [0, len.saturating_sub(1), len] {
+        let _ = Base16384::decode_to_slice(data, &mut buf[..n]);
+    }
+    let _ = Base16384::decode_iter(data.iter().cloned()).count();
+    let _ = Base16384::decode_chars(char::decode_utf16(data.iter().cloned()).flatten()).count();
+    let _ = Base16384::decode_lenient(data, DEFAULT_IGNORE);
+    let _ = Base16384::decode_lossy(data, 0);
+    let _ = Base16384::inspect(data).to_string();
+    for &config in configs {
+        let _ = Base16384::decode_len_with_config(data, config);
+        let _ = Base16384::decode_with_config(data, config);
+        let _ = Base16384::decode_to_slice_with_config(data, &mut buf[..len], config);
+    }
+
+    for room in [0, 7] {
+        let mut state = DecoderState::new();
+        let mut input = data;
+        while let Ok((consumed, _)) = state.update(input, &mut buf[..room]) {
+            if consumed == 0 {
+                break;
+            }
+            input = &input[consumed..];
+        }
+        let _ = state.finalize(&mut buf[..room]);
+    }
+
+    let be = data
+        .iter()
+        .flat_map(|c| c.to_be_bytes())
+        .collect::<Vec<_>>();
+    let le = data
+        .iter()
+        .flat_map(|c| c.to_le_bytes())
+        .collect::<Vec<_>>();
+    for bytes in [&be, &le] {
+        decode_bytes(bytes);
+    }
+
+    if let Ok(text) = String::from_utf16(data) {
+        decode_str(&text, configs);
+    }
+}
+
+fn decode_str(text: &str, configs: &[Config]) {
+    let mut buf = [0u8; 64];
+    let _ = Base16384Utf8::decode(text);
+    let _ = Base16384Utf8::validate(text);
+    let _ = Base16384Utf8::decode_to_slice(text, &mut buf[..0]);
+    let _ = Base16384Utf8::decode_to_slice(text, &mut buf);
+    let _ = Base16384Utf8::decode_lenient(text, DEFAULT_IGNORE);
+    let _ = Base16384Utf8::decode_lossy(text, 0);
+    let _ = Base16384Utf8::inspect(text).to_string();
+    let _ = Base16384Utf8::find_payloads(text).count();
+    let _ = detect(text);
+    for &config in configs {
+        let _ = Base16384Utf8::decode_len_with_config(text, config);
+        let _ = Base16384Utf8::decode_with_config(text, config);
+        let _ = Base16384Utf8::decode_to_slice_with_config(text, &mut buf[..0], config);
+    }
+}
+
+fn decode_bytes(bytes: &[u8]) {
+    let mut buf = [0u8; 64];
+    let _ = <Base16384Utf8 as Codec>::decode_to_slice(bytes, &mut buf[..0]);
+    let _ = <Base16384Utf8 as Codec>::decode_to_slice(bytes, &mut buf);
+    let _ = Base16384::decode_utf16(bytes);
+    let _ = Base16384::decode_utf16be(bytes);
+    let _ = Base16384::decode_utf16le(bytes);
+    let _ = decode_auto(bytes);
+    let _ = detect_bytes(bytes);
+
+    let mut out = Vec::new();
+    let _ = Base16384Decoder::new(bytes).read_to_end(&mut out);
+    let _ = Base16384Utf8Decoder::new(bytes).read_to_end(&mut out);
+    let mut writer = DecoderWriter::new(Vec::new());
+    if writer.write_all(bytes).is_ok() {
+        let _ = writer.finish();
+    }
+    let mut writer = Utf8DecoderWriter::new(Vec::new());
+    if writer.write_all(bytes).is_ok() {
+        let _ = writer.finish();
+    }
+}
+
+#[test]
+fn decode_units_never_panics() {
+    let configs = configs();
+    for n in 0..=3 {
+        for_each_input(UNITS, n, &mut |data| decode_units(data, &configs));
+    }
+    for n in 4..=6 {
+        for_each_input(FEW_UNITS, n, &mut |data| decode_units(data, &configs));
+    }
+}
+
+#[test]
+fn decode_bytes_never_panics() {
+    for n in 0..=5 {
+        for_each_input(BYTES, n, &mut decode_bytes);
+    }
+    // Up to 24 bytes, to reach the full chunks of UTF-8 and the buffering of the
+    // readers and writers.
+    let mut bytes = Vec::new();
+    for n in 5..=6 {
+        for_each_input(FRAGMENTS, n, &mut |fragments| {
+            bytes.clear();
+            fragments.iter().for_each(|f| bytes.extend_from_slice(f));
+            decode_bytes(&bytes);
+            if let Ok(text) = std::str::from_utf8(&bytes) {
+                decode_str(text, &[]);
+            }
+        });
+    }
+}
+
+#[test]
+fn decode_len_never_panics() {
+    for data_len in (0..20).chain([usize::MAX - 1, usize::MAX]) {
+        for padding in (0..=u16::MAX).map(Some).chain([None]) {
+            let _ = Base16384::decode_len(data_len, padding);
+            let _ = Base16384::try_decode_len(data_len, padding);
+            let _ = Base16384Utf8::decode_len(data_len, padding);
+            let _ = Base16384Utf8::try_decode_len(data_len, padding);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::io::{self, Read};
+
+use base16384::error::Base16384DecodeError;
+use base16384::read::{EncoderReader, Utf8EncoderReader};
+use base16384::{Base16384, Base16384Decoder, Base16384Utf8, Base16384Utf8Decoder};
+
+/// A reader yielding at most `step` bytes per read.
+struct Trickle<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+fn utf16be(data: &[u16]) -> Vec<u8> {
+    data.iter().flat_map(|c| c.to_be_bytes()).collect()
+}
+
+fn decode_error(mut decoder: impl Read) -> Base16384DecodeError {
+    let mut decoded = Vec::new();
+    let err = decoder.read_to_end(&mut decoded).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    *err.into_inner().unwrap().downcast().unwrap()
+}
+
+#[test]
+fn decoder_split_reads() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..30 {
+        let text = utf16be(&Base16384::encode(&data[..len]));
+        for step in 1..12 {
+            let mut decoder = Base16384Decoder::new(Trickle { data: &text, step });
+            let mut decoded = Vec::new();
+            let mut buf = [0u8; 5];
+            loop {
+                let n = decoder.read(&mut buf[..step.min(5)]).unwrap();
+                if n == 0 {
+                    break;
+                }
+                decoded.extend_from_slice(&buf[..n]);
+            }
+            assert_eq!(decoded, &data[..len]);
+        }
+    }
+}
+
+#[test]
+fn decoder_large() {
+    let data = (0..100_000)
+        .map(|i| (i * 31 % 251) as u8)
+        .collect::<Vec<_>>();
+    let text = utf16be(&Base16384::encode(&data));
+    let mut decoded = Vec::new();
+    Base16384Decoder::new(&text[..])
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn decoder_errors() {
+    let mut text = utf16be(&Base16384::encode(&[0u8; 20]));
+    text[2 * 9] = 0;
+    assert_eq!(
+        decode_error(Base16384Decoder::new(&text[..])),
+        Base16384DecodeError::InvalidCharacter {
+            index: 9,
+            value: 0,
+            padding_position: false,
+        }
+    );
+
+    let mut text = utf16be(&Base16384::encode(&[0u8; 20]));
+    text.push(0);
+    assert_eq!(
+        decode_error(Base16384Decoder::new(&text[..])),
+        Base16384DecodeError::InvalidLength
+    );
+
+    let text = utf16be(&Base16384::encode(&[0u8; 3]));
+    assert_eq!(
+        decode_error(Base16384Decoder::new(&text[..text.len() - 2])),
+        Base16384DecodeError::InvalidLength
+    );
+
+    let mut text = utf16be(&Base16384::encode(&[0u8; 12]));
+    text.extend(utf16be(&Base16384::encode(&[0u8; 7])));
+    assert_eq!(
+        decode_error(Base16384Decoder::new(&text[..])),
+        Base16384DecodeError::PaddingNotAtEnd { index: 7 }
+    );
+}
+
+#[test]
+fn utf8_decoder_split_reads() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..30 {
+        let text = Base16384Utf8::encode(&data[..len]);
+        for step in 1..16 {
+            let mut decoder = Base16384Utf8Decoder::new(Trickle {
+                data: text.as_bytes(),
+                step,
+            });
+            let mut decoded = Vec::new();
+            let mut buf = [0u8; 5];
+            loop {
+                let n = decoder.read(&mut buf[..step.min(5)]).unwrap();
+                if n == 0 {
+                    break;
+                }
+                decoded.extend_from_slice(&buf[..n]);
+            }
+            assert_eq!(decoded, &data[..len]);
+        }
+    }
+}
+
+#[test]
+fn utf8_decoder_errors() {
+    let mut text = Base16384Utf8::encode(&[0u8; 20]).into_bytes();
+    text[3 * 9 + 1] = b'a';
+    assert_eq!(
+        decode_error(Base16384Utf8Decoder::new(&text[..])),
+        Base16384DecodeError::InvalidCharacter {
+            index: 27,
+            value: 0xfffd,
+            padding_position: false,
+        }
+    );
+
+    let text = Base16384Utf8::encode(&[0u8; 20]).into_bytes();
+    assert_eq!(
+        decode_error(Base16384Utf8Decoder::new(&text[..text.len() - 1])),
+        Base16384DecodeError::TruncatedCharacter { index: 36 }
+    );
+
+    let mut text = Base16384Utf8::encode(&[0u8; 12]).into_bytes();
+    text.extend(Base16384Utf8::encode(&[0u8; 7]).into_bytes());
+    assert_eq!(
+        decode_error(Base16384Utf8Decoder::new(&text[..])),
+        Base16384DecodeError::PaddingNotAtEnd { index: 21 }
+    );
+}
+
+#[test]
+fn utf8_decoder_errors_match_decode() {
+    // The emoji straddles the end of the first 12-byte chunk.
+    for text in ["婌焳廔😀…", "婌焳廔😀萷尀㴁", "婌焳廔萷尀㴁婌焳廔😀婌焳"]
+    {
+        let err = Base16384Utf8::decode(text).unwrap_err();
+        for step in [1, 5, 1024] {
+            let reader = Trickle {
+                data: text.as_bytes(),
+                step,
+            };
+            assert_eq!(decode_error(Base16384Utf8Decoder::new(reader)), err);
+        }
+    }
+    assert_eq!(
+        Base16384Utf8::decode("婌焳廔😀…").unwrap_err(),
+        Base16384DecodeError::InvalidCharacter {
+            index: 9,
+            value: '😀' as u32,
+            padding_position: false,
+        }
+    );
+}
+
+fn read_all(mut reader: impl Read, step: usize) -> Vec<u8> {
+    let mut result = Vec::new();
+    let mut buf = vec![0u8; step];
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            return result;
+        }
+        result.extend_from_slice(&buf[..n]);
+    }
+}
+
+#[test]
+fn encoder_reader_split_reads() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..30 {
+        let text = utf16be(&Base16384::encode(&data[..len]));
+        let utf8 = Base16384Utf8::encode(&data[..len]);
+        for step in 1..16 {
+            let reader = Trickle {
+                data: &data[..len],
+                step,
+            };
+            assert_eq!(read_all(EncoderReader::new(reader), step), text);
+
+            let reader = Trickle {
+                data: &data[..len],
+                step,
+            };
+            assert_eq!(
+                read_all(Utf8EncoderReader::new(reader), step),
+                utf8.as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn encoder_reader_large() {
+    let data = (0..100_000)
+        .map(|i| (i * 31 % 251) as u8)
+        .collect::<Vec<_>>();
+    let mut encoded = Vec::new();
+    EncoderReader::new(&data[..])
+        .read_to_end(&mut encoded)
+        .unwrap();
+    assert_eq!(encoded, utf16be(&Base16384::encode(&data)));
+
+    let mut encoded = String::new();
+    Utf8EncoderReader::new(&data[..])
+        .read_to_string(&mut encoded)
+        .unwrap();
+    assert_eq!(encoded, Base16384Utf8::encode(&data));
+}
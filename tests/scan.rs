@@ -0,0 +1,61 @@
+use base16384::Base16384Utf8;
+
+#[test]
+fn find_embedded() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 1..40 {
+        let data = &data[..len];
+        let payload = Base16384Utf8::encode(data);
+        let text = format!("file ({} bytes): {}, and again:\n{}", len, payload, payload);
+        let spans = Base16384Utf8::find_payloads(&text).collect::<Vec<_>>();
+        if len % 7 == 0 && len < 21 {
+            // Too short to tell apart from prose without the padding.
+            assert!(spans.is_empty());
+            let spans = Base16384Utf8::find_payloads(&text).min_unpadded_chars(4);
+            assert_eq!(spans.count(), 2);
+            continue;
+        }
+        assert_eq!(spans.len(), 2);
+        for span in spans {
+            assert_eq!(&text[span.range], payload);
+            assert_eq!(span.data, data);
+        }
+    }
+}
+
+#[test]
+fn skip_prose() {
+    let texts = [
+        "我们需要一个新的方案来处理这个问题。",
+        "今天天气很好，我们一起去公园散步吧！",
+        "中华人民共和国成立于一九四九年十月一日",
+        "春眠不觉晓，处处闻啼鸟。夜来风雨声，花落知多少。",
+    ];
+    for text in texts {
+        assert_eq!(Base16384Utf8::find_payloads(text).count(), 0, "{}", text);
+    }
+
+    // Sentences of 12 and 16 characters between punctuation decode strictly, but are
+    // mostly common hanzi.
+    for text in [
+        "。今天天气很好我们去公园吧，",
+        "“他说这个国家的人民生活得很好了吧”",
+    ] {
+        assert_eq!(Base16384Utf8::find_payloads(text).count(), 0, "{}", text);
+    }
+
+    // A payload is still found next to prose with punctuation in between.
+    let text = "文件：婌焳廔萷尀㴁。谢谢";
+    let spans = Base16384Utf8::find_payloads(text).collect::<Vec<_>>();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].data, b"12345678");
+}
+
+#[test]
+fn skip_invalid() {
+    // Not canonical: the discarded bit of '婍' is set.
+    assert_eq!(Base16384Utf8::find_payloads("x 婍㴁 y").count(), 0);
+    // Invalid length for the padding.
+    assert_eq!(Base16384Utf8::find_payloads("x 婌焳㴁 y").count(), 0);
+    assert_eq!(Base16384Utf8::find_payloads("x 婀㴁 y").count(), 1);
+}
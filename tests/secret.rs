@@ -0,0 +1,56 @@
+#![cfg(feature = "zeroize")]
+
+use base16384::error::Base16384DecodeError;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn decode_zeroizing_exact_capacity() {
+    // The secret is decoded in place, so no reallocation leaves a copy behind.
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..60 {
+        let encoded = Base16384::encode(&data[..len]);
+        let decoded = Base16384::decode_zeroizing(&encoded).unwrap();
+        assert_eq!(*decoded, &data[..len]);
+        assert_eq!(decoded.capacity(), len);
+        let encoded = Base16384Utf8::encode(&data[..len]);
+        let decoded = Base16384Utf8::decode_zeroizing(&encoded).unwrap();
+        assert_eq!(*decoded, &data[..len]);
+        assert_eq!(decoded.capacity(), len);
+    }
+}
+
+#[test]
+fn decode_zeroizing_error() {
+    assert_eq!(
+        Base16384Utf8::decode_zeroizing("婌焳廔萷x尀㴁").unwrap_err(),
+        Base16384DecodeError::InvalidCharacter {
+            index: 12,
+            value: 'x' as u32,
+            padding_position: false,
+        }
+    );
+}
+
+#[test]
+fn decode_into_vec_error_keeps_vector() {
+    let mut out = b"abc".to_vec();
+    assert!(Base16384Utf8::decode_into_vec("婌焳廔萷x尀㴁", &mut out).is_err());
+    assert_eq!(out, b"abc");
+}
+
+#[test]
+fn decode_into_vec_error_wipes_spare_capacity() {
+    // The first chunk is decoded before the error is found in the second one.
+    let text = "婌焳廔萷婌焳ａ萷尀㴁";
+    let units = text.encode_utf16().collect::<Vec<_>>();
+    let mut out = Vec::with_capacity(32);
+    assert!(Base16384::decode_into_vec(&units, &mut out).is_err());
+    // SAFETY: the spare capacity was wiped, so it is initialized.
+    let spare = unsafe { std::slice::from_raw_parts(out.as_ptr(), 15) };
+    assert_eq!(spare, [0; 15]);
+
+    let mut out = Vec::with_capacity(32);
+    assert!(Base16384Utf8::decode_into_vec(text, &mut out).is_err());
+    let spare = unsafe { std::slice::from_raw_parts(out.as_ptr(), 15) };
+    assert_eq!(spare, [0; 15]);
+}
@@ -0,0 +1,88 @@
+use base16384::error::Base16384DecodeError;
+use base16384::state::{DecoderState, EncoderState};
+use base16384::Base16384;
+
+fn encode(data: &[u8], step: usize, room: usize) -> Vec<u16> {
+    let mut state = EncoderState::new();
+    let mut result = Vec::new();
+    let mut buf = vec![0u16; room.max(5)];
+    for mut part in data.chunks(step) {
+        while !part.is_empty() {
+            let (consumed, produced) = state.update(part, &mut buf[..room]);
+            result.extend_from_slice(&buf[..produced]);
+            part = &part[consumed..];
+        }
+    }
+    let produced = state.finalize(&mut buf);
+    result.extend_from_slice(&buf[..produced]);
+    result
+}
+
+fn decode(data: &[u16], step: usize, room: usize) -> Result<Vec<u8>, Base16384DecodeError> {
+    let mut state = DecoderState::new();
+    let mut result = Vec::new();
+    let mut buf = vec![0u8; room.max(7)];
+    for mut part in data.chunks(step) {
+        while !part.is_empty() {
+            let (consumed, produced) = state.update(part, &mut buf[..room])?;
+            result.extend_from_slice(&buf[..produced]);
+            part = &part[consumed..];
+        }
+    }
+    let produced = state.finalize(&mut buf)?;
+    result.extend_from_slice(&buf[..produced]);
+    Ok(result)
+}
+
+#[test]
+fn encoder_state() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let expected = Base16384::encode(&data[..len]);
+        for step in 1..10 {
+            for room in [4, 5, 8, 13] {
+                assert_eq!(encode(&data[..len], step, room), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn decoder_state() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let encoded = Base16384::encode(&data[..len]);
+        for step in 1..10 {
+            for room in [7, 8, 14, 20] {
+                assert_eq!(decode(&encoded, step, room).unwrap(), &data[..len]);
+            }
+        }
+    }
+}
+
+#[test]
+fn decoder_state_errors() {
+    let mut encoded = Base16384::encode(&[0u8; 20]);
+    encoded[9] = 0;
+    assert_eq!(
+        decode(&encoded, 3, 7),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 9,
+            value: 0,
+            padding_position: false,
+        })
+    );
+
+    let encoded = Base16384::encode(&[0u8; 20]);
+    assert_eq!(
+        decode(&encoded[..encoded.len() - 2], 3, 7),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+
+    let mut encoded = Base16384::encode(&[0u8; 12]);
+    encoded.extend(Base16384::encode(&[0u8; 7]));
+    assert_eq!(
+        decode(&encoded, 3, 7),
+        Err(Base16384DecodeError::PaddingNotAtEnd { index: 7 })
+    );
+}
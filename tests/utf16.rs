@@ -0,0 +1,130 @@
+use base16384::error::Base16384DecodeError;
+use base16384::Base16384;
+
+#[test]
+fn encode_utf16() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..40 {
+        let encoded = Base16384::encode(&data[..len]);
+        let be = encoded
+            .iter()
+            .flat_map(|c| c.to_be_bytes())
+            .collect::<Vec<_>>();
+        let le = encoded
+            .iter()
+            .flat_map(|c| c.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        assert_eq!(Base16384::encode_utf16be(&data[..len], false), be);
+        assert_eq!(Base16384::encode_utf16le(&data[..len], false), le);
+        assert_eq!(
+            Base16384::encode_utf16be(&data[..len], true),
+            [&[0xFE, 0xFF][..], &be].concat()
+        );
+        assert_eq!(
+            Base16384::encode_utf16le(&data[..len], true),
+            [&[0xFF, 0xFE][..], &le].concat()
+        );
+    }
+}
+
+#[test]
+fn decode_utf16() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in (0..40).chain(600..620) {
+        let data = &data.repeat(3)[..len];
+        for bom in [false, true] {
+            let be = Base16384::encode_utf16be(data, bom);
+            assert_eq!(Base16384::decode_utf16be(&be).unwrap(), data);
+            assert_eq!(Base16384::decode_utf16(&be).unwrap(), data);
+
+            let le = Base16384::encode_utf16le(data, bom);
+            assert_eq!(Base16384::decode_utf16le(&le).unwrap(), data);
+        }
+        let le = Base16384::encode_utf16le(data, true);
+        assert_eq!(Base16384::decode_utf16(&le).unwrap(), data);
+    }
+}
+
+#[test]
+fn c_tool_output() {
+    // `base16384 -e` on a file containing "12345678".
+    let file = [
+        0xFE, 0xFF, 0x5A, 0x4C, 0x71, 0x33, 0x5E, 0xD4, 0x84, 0x37, 0x5C, 0x00, 0x3D, 0x01,
+    ];
+    assert_eq!(Base16384::encode_utf16be(b"12345678", true), file);
+    assert_eq!(Base16384::decode_utf16(&file).unwrap(), b"12345678");
+}
+
+#[test]
+fn decode_utf16_error() {
+    let mut be = Base16384::encode_utf16be(b"12345678", true);
+    assert_eq!(
+        Base16384::decode_utf16be(&be[..be.len() - 1]),
+        Err(Base16384DecodeError::InvalidLength)
+    );
+
+    be[6] = 0x20;
+    assert_eq!(
+        Base16384::decode_utf16be(&be),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 6,
+            value: 0x20d4,
+            padding_position: false,
+        })
+    );
+    assert_eq!(
+        Base16384::decode_utf16be(&be[2..]),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 4,
+            value: 0x20d4,
+            padding_position: false,
+        })
+    );
+
+    // A byte order mark of the other endianness is not skipped.
+    let mut le = Base16384::encode_utf16le(b"12", false);
+    le.splice(..0, [0xFE, 0xFF, 0xFF, 0xFE]);
+    assert_eq!(
+        Base16384::decode_utf16be(&le),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 2,
+            value: 0xfffe,
+            padding_position: false,
+        })
+    );
+}
+
+#[test]
+fn decode_utf16_first_error() {
+    // The invalid character is reported before the invalid padding after it.
+    let units = [0x41, 0x4E00, 0x3D01];
+    let expected = Base16384DecodeError::InvalidCharacter {
+        index: 0,
+        value: 0x41,
+        padding_position: false,
+    };
+    assert_eq!(Base16384::decode(&units), Err(expected.clone()));
+
+    let be = units
+        .iter()
+        .flat_map(|c| c.to_be_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(Base16384::decode_utf16be(&be), Err(expected.clone()));
+    let le = units
+        .iter()
+        .flat_map(|c| c.to_le_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(Base16384::decode_utf16le(&le), Err(expected));
+
+    let mut be = be;
+    be.splice(..0, [0xFE, 0xFF]);
+    assert_eq!(
+        Base16384::decode_utf16(&be),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 2,
+            value: 0x41,
+            padding_position: false,
+        })
+    );
+}
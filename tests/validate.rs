@@ -0,0 +1,102 @@
+use base16384::error::Base16384DecodeError;
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn validate_prefixes() {
+    // Every prefix of valid data is accepted or rejected as by decoding it.
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..60 {
+        let encoded = Base16384::encode(&data[..len]);
+        assert_eq!(Base16384::validate(&encoded), Ok(len));
+        for end in 0..encoded.len() {
+            let prefix = &encoded[..end];
+            let decoded = Base16384::decode(prefix).map(|decoded| decoded.len());
+            assert_eq!(Base16384::validate(prefix), decoded);
+        }
+
+        let encoded = Base16384Utf8::encode(&data[..len]);
+        assert_eq!(Base16384Utf8::validate(&encoded), Ok(len));
+        for end in (0..encoded.len()).step_by(3) {
+            let prefix = &encoded[..end];
+            let decoded = Base16384Utf8::decode(prefix).map(|decoded| decoded.len());
+            assert_eq!(Base16384Utf8::validate(prefix), decoded);
+        }
+    }
+}
+
+#[test]
+fn validate_matches_decode() {
+    let encoded = Base16384::encode(b"1234567812345");
+    for i in 0..encoded.len() {
+        for c in [
+            0x0041, 0x3D00, 0x3D01, 0x3D07, 0x4DFF, 0x4E00, 0x8DFF, 0x8E00, 0xD800,
+        ] {
+            let mut data = encoded.clone();
+            data[i] = c;
+            for data in [&data[..], &data[..i + 1]] {
+                let decoded = Base16384::decode(data);
+                assert_eq!(
+                    Base16384::validate(data).is_ok(),
+                    decoded.is_ok(),
+                    "{:x?}",
+                    data
+                );
+                if let Ok(decoded) = decoded {
+                    assert_eq!(Base16384::validate(data), Ok(decoded.len()));
+                }
+
+                let text = String::from_utf16_lossy(data);
+                let decoded = Base16384Utf8::decode(&text);
+                assert_eq!(Base16384Utf8::validate(&text).is_ok(), decoded.is_ok());
+                if let Ok(decoded) = decoded {
+                    assert_eq!(Base16384Utf8::validate(&text), Ok(decoded.len()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn validate_errors() {
+    let data = "婌焳廔萷婌焳廔x".encode_utf16().collect::<Vec<_>>();
+    assert_eq!(
+        Base16384::validate(&data),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 7,
+            value: 'x' as u32,
+            padding_position: true,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::validate("婌焳廔萷婌焳x廔"),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 18,
+            value: 'x' as u32,
+            padding_position: false,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::validate("婌焳廔萷婌焳😀"),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 18,
+            value: '😀' as u32,
+            padding_position: true,
+        })
+    );
+    assert_eq!(
+        Base16384Utf8::validate("婌焳廔萷婌焳ａ廔"),
+        Err(Base16384DecodeError::InvalidCharacter {
+            index: 18,
+            value: 'ａ' as u32,
+            padding_position: false,
+        })
+    );
+    assert_eq!(
+        Base16384::validate(&[0x3D01]),
+        Err(Base16384DecodeError::InvalidPadding {
+            index: 0,
+            value: 0x3d01,
+        })
+    );
+    assert_eq!(Base16384::validate(&[0x3D00]), Ok(0));
+}
@@ -0,0 +1,55 @@
+use base16384::lenient::DEFAULT_IGNORE;
+use base16384::wrap::{LineEnding, LineWrap};
+use base16384::{Base16384, Base16384Utf8};
+
+#[test]
+fn wrap_roundtrip() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in (0..40).chain(250..256) {
+        let data = &data[..len];
+        for n in 1..10 {
+            for line_ending in [LineEnding::Lf, LineEnding::CrLf] {
+                let wrap = LineWrap::chars(n).with_line_ending(line_ending);
+                let text = Base16384Utf8::encode_wrapped(data, wrap);
+                assert_eq!(
+                    text.replace(line_ending.as_str(), ""),
+                    Base16384Utf8::encode(data)
+                );
+                for line in text.split(line_ending.as_str()) {
+                    assert!(line.chars().count() <= n);
+                }
+                assert!(!text.ends_with('\n'));
+                assert_eq!(
+                    Base16384Utf8::decode_lenient(&text, DEFAULT_IGNORE).unwrap(),
+                    data
+                );
+
+                let encoded = Base16384::encode_wrapped(data, wrap);
+                assert_eq!(encoded, text.encode_utf16().collect::<Vec<_>>());
+                assert_eq!(
+                    Base16384::decode_lenient(&encoded, DEFAULT_IGNORE).unwrap(),
+                    data
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn wrap_columns() {
+    assert_eq!(LineWrap::columns(80).chars_per_line(), 40);
+    assert_eq!(LineWrap::columns(79).chars_per_line(), 39);
+    assert_eq!(LineWrap::columns(1).chars_per_line(), 1);
+    assert_eq!(LineWrap::columns(0).chars_per_line(), 1);
+
+    let text = Base16384Utf8::encode_wrapped(&[0xFF; 70], LineWrap::columns(20));
+    let lines = text.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|line| line.chars().count() == 10));
+}
+
+#[test]
+#[should_panic]
+fn wrap_zero_chars() {
+    LineWrap::chars(0);
+}
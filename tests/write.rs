@@ -0,0 +1,148 @@
+use std::io::{self, Write};
+
+use base16384::error::Base16384DecodeError;
+use base16384::write::{DecoderWriter, Utf8DecoderWriter};
+
+use base16384::{Base16384, Base16384Encoder, Base16384Utf8, Base16384Utf8Encoder};
+
+fn utf16be(data: &[u16]) -> Vec<u8> {
+    data.iter().flat_map(|c| c.to_be_bytes()).collect()
+}
+
+#[test]
+fn encoder_split_writes() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..30 {
+        for step in 1..9 {
+            let mut encoder = Base16384Encoder::new(Vec::new());
+            for part in data[..len].chunks(step) {
+                encoder.write_all(part).unwrap();
+            }
+            let encoded = encoder.finish().unwrap();
+            assert_eq!(encoded, utf16be(&Base16384::encode(&data[..len])));
+        }
+    }
+}
+
+#[test]
+fn encoder_large() {
+    let data = (0..100_000)
+        .map(|i| (i * 31 % 251) as u8)
+        .collect::<Vec<_>>();
+    let mut encoder = Base16384Encoder::new(Vec::new());
+    encoder.write_all(&data[..3]).unwrap();
+    encoder.write_all(&data[3..]).unwrap();
+    let encoded = encoder.finish().unwrap();
+    assert_eq!(encoded, utf16be(&Base16384::encode(&data)));
+}
+
+#[test]
+fn encoder_finish_on_drop() {
+    let mut encoded = Vec::new();
+    {
+        let mut encoder = Base16384Encoder::new(&mut encoded);
+        encoder.write_all(b"12345678").unwrap();
+    }
+    assert_eq!(encoded, utf16be(&Base16384::encode(b"12345678")));
+}
+
+#[test]
+fn utf8_encoder_split_writes() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..30 {
+        for step in 1..9 {
+            let mut encoder = Base16384Utf8Encoder::new(Vec::new());
+            for part in data[..len].chunks(step) {
+                encoder.write_all(part).unwrap();
+            }
+            let encoded = encoder.finish().unwrap();
+            assert_eq!(encoded, Base16384Utf8::encode(&data[..len]).as_bytes());
+        }
+    }
+}
+
+#[test]
+fn decoder_writer_split_writes() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for len in 0..30 {
+        let text = utf16be(&Base16384::encode(&data[..len]));
+        let utf8 = Base16384Utf8::encode(&data[..len]);
+        for step in 1..16 {
+            let mut decoder = DecoderWriter::new(Vec::new());
+            for part in text.chunks(step) {
+                decoder.write_all(part).unwrap();
+            }
+            assert_eq!(decoder.finish().unwrap(), &data[..len]);
+
+            let mut decoder = Utf8DecoderWriter::new(Vec::new());
+            for part in utf8.as_bytes().chunks(step) {
+                decoder.write_all(part).unwrap();
+            }
+            assert_eq!(decoder.finish().unwrap(), &data[..len]);
+        }
+    }
+}
+
+#[test]
+fn decoder_writer_large() {
+    let data = (0..100_000)
+        .map(|i| (i * 31 % 251) as u8)
+        .collect::<Vec<_>>();
+    let mut decoder = DecoderWriter::new(Vec::new());
+    decoder
+        .write_all(&utf16be(&Base16384::encode(&data)))
+        .unwrap();
+    assert_eq!(decoder.finish().unwrap(), data);
+
+    let mut decoder = Utf8DecoderWriter::new(Vec::new());
+    decoder
+        .write_all(Base16384Utf8::encode(&data).as_bytes())
+        .unwrap();
+    assert_eq!(decoder.finish().unwrap(), data);
+}
+
+#[test]
+fn decoder_writer_errors() {
+    fn decode_error(err: io::Error) -> Base16384DecodeError {
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        *err.into_inner().unwrap().downcast().unwrap()
+    }
+
+    let mut text = utf16be(&Base16384::encode(&[0u8; 2000]));
+    text[2 * 999] = 0;
+    let mut decoder = DecoderWriter::new(Vec::new());
+    let err = decoder.write_all(&text).and(decoder.flush()).unwrap_err();
+    assert_eq!(
+        decode_error(err),
+        Base16384DecodeError::InvalidCharacter {
+            index: 999,
+            value: 0,
+            padding_position: false,
+        }
+    );
+
+    let text = utf16be(&Base16384::encode(&[0u8; 20]));
+    let mut decoder = DecoderWriter::new(Vec::new());
+    decoder.write_all(&text[..text.len() - 1]).unwrap();
+    assert_eq!(
+        decode_error(decoder.finish().unwrap_err()),
+        Base16384DecodeError::InvalidLength
+    );
+
+    let mut decoder = Utf8DecoderWriter::new(Vec::new());
+    decoder.write_all("婌焳廔萷尀".as_bytes()).unwrap();
+    assert_eq!(
+        decode_error(decoder.finish().unwrap_err()),
+        Base16384DecodeError::InvalidLength
+    );
+
+    // The emoji straddles the end of the first 12-byte chunk.
+    for text in ["婌焳廔😀…", "婌焳廔😀萷尀㴁"] {
+        let mut decoder = Utf8DecoderWriter::new(Vec::new());
+        decoder.write_all(text.as_bytes()).unwrap();
+        assert_eq!(
+            decode_error(decoder.finish().unwrap_err()),
+            Base16384Utf8::decode(text).unwrap_err()
+        );
+    }
+}